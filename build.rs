@@ -0,0 +1,77 @@
+//! Generates an acceptance test for every part of every input under `tests/fixtures`.
+//!
+//! Each `tests/fixtures/day-N/` directory holds inputs named `input.txt` or `<name>-input.txt`,
+//! with answers in `expected-part-P.txt` or `<name>-expected-part-P.txt` alongside. Parts whose
+//! answer is still the placeholder written by `new-day` get ignored tests; a missing answer file
+//! fails its test. The generated file is included by `tests/acceptance.rs`, which defines the
+//! `fixture_test!` macro it calls.
+
+use std::{env, fs, path::Path};
+
+const FIXTURES_DIR: &str = "tests/fixtures";
+
+/// Must match `runner::PLACEHOLDER_ANSWER`, which a build script can't use
+const PLACEHOLDER_ANSWER: &str = "unsolved";
+
+fn main() {
+    println!("cargo:rerun-if-changed={}", FIXTURES_DIR);
+
+    let mut fixtures = Vec::new();
+    // Without a fixtures directory there are no tests to generate, but the file must still exist
+    let entries = fs::read_dir(FIXTURES_DIR).into_iter().flatten();
+    for entry in entries {
+        let path = entry.unwrap().path();
+        let day = match path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_prefix("day-"))
+            .and_then(|day| day.parse::<u8>().ok())
+        {
+            Some(day) => day,
+            None => continue,
+        };
+        for entry in fs::read_dir(&path).unwrap() {
+            let file_name = entry.unwrap().file_name().into_string().unwrap();
+            let name = match file_name.as_str() {
+                "input.txt" => "input",
+                other => match other.strip_suffix("-input.txt") {
+                    Some(name) => name,
+                    None => continue,
+                },
+            };
+            fixtures.push((day, path.clone(), name.to_owned()));
+        }
+    }
+    fixtures.sort();
+
+    let mut out = String::new();
+    for (day, dir, name) in fixtures {
+        for part in [1, 2] {
+            let expected_name = if name == "input" {
+                format!("expected-part-{}.txt", part)
+            } else {
+                format!("{}-expected-part-{}.txt", name, part)
+            };
+            let unsolved = fs::read_to_string(dir.join(expected_name))
+                .map(|expected| expected.trim() == PLACEHOLDER_ANSWER)
+                .unwrap_or(false);
+            let attrs = if unsolved {
+                "#[ignore = \"no expected answer yet\"] "
+            } else {
+                ""
+            };
+            out.push_str(&format!(
+                "fixture_test!({}day{:02}_part{}_{}, {}, {}, {:?});\n",
+                attrs,
+                day,
+                part,
+                name.replace(|c: char| !c.is_ascii_alphanumeric(), "_"),
+                day,
+                part,
+                name
+            ));
+        }
+    }
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("fixture_tests.rs");
+    fs::write(out_path, out).unwrap();
+}
@@ -0,0 +1,160 @@
+use std::fmt::Display;
+
+/// A puzzle answer.
+///
+/// Integers of any primitive width convert into an `Answer` with `into()`, and numbers too large
+/// for any of them can be given as decimal digits with [`Answer::from_decimal`]. Every number is
+/// stored one way only, so equal numbers compare equal whatever type they were computed in.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Answer(Repr);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Repr {
+    Unsigned(u128),
+    /// Only used for negative numbers
+    Signed(i128),
+    /// Decimal digits, with a `-` if negative and no leading zeros. Only used for numbers that
+    /// don't fit in a `u128` or `i128`.
+    Big(String),
+    /// Anything that isn't a number, such as letters rendered over several lines
+    Text(String),
+}
+
+impl Answer {
+    /// A number written in decimal, with an optional leading `-`, of any size. Returns `None` if
+    /// `digits` isn't one.
+    pub fn from_decimal(digits: &str) -> Option<Self> {
+        let (negative, magnitude) = match digits.strip_prefix('-') {
+            Some(magnitude) => (true, magnitude),
+            None => (false, digits),
+        };
+        if magnitude.is_empty() || !magnitude.bytes().all(|byte| byte.is_ascii_digit()) {
+            return None;
+        }
+        let magnitude = magnitude.trim_start_matches('0');
+        let magnitude = if magnitude.is_empty() { "0" } else { magnitude };
+        let answer = match (negative, magnitude.parse::<u128>()) {
+            (false, Ok(n)) => n.into(),
+            (true, Ok(n)) if n <= i128::MIN.unsigned_abs() => (n as i128).wrapping_neg().into(),
+            (false, Err(_)) => Answer(Repr::Big(magnitude.to_owned())),
+            (true, _) => Answer(Repr::Big(format!("-{}", magnitude))),
+        };
+        Some(answer)
+    }
+
+    pub fn as_u128(&self) -> Option<u128> {
+        match self.0 {
+            Repr::Unsigned(n) => Some(n),
+            _ => None,
+        }
+    }
+
+    pub fn as_i128(&self) -> Option<i128> {
+        match self.0 {
+            Repr::Unsigned(n) => n.try_into().ok(),
+            Repr::Signed(n) => Some(n),
+            Repr::Big(_) | Repr::Text(_) => None,
+        }
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        self.as_u128().and_then(|n| n.try_into().ok())
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        self.as_i128().and_then(|n| n.try_into().ok())
+    }
+
+    pub fn as_text(&self) -> Option<&str> {
+        match &self.0 {
+            Repr::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.0 {
+            Repr::Unsigned(n) => n.fmt(f),
+            Repr::Signed(n) => n.fmt(f),
+            Repr::Big(digits) => digits.fmt(f),
+            Repr::Text(text) => text.fmt(f),
+        }
+    }
+}
+
+macro_rules! from_unsigned {
+    ($($int:ty),*) => {
+        $(
+            impl From<$int> for Answer {
+                fn from(n: $int) -> Self {
+                    Answer(Repr::Unsigned(n as u128))
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! from_signed {
+    ($($int:ty),*) => {
+        $(
+            impl From<$int> for Answer {
+                fn from(n: $int) -> Self {
+                    if n < 0 {
+                        Answer(Repr::Signed(n as i128))
+                    } else {
+                        Answer(Repr::Unsigned(n as u128))
+                    }
+                }
+            }
+        )*
+    };
+}
+
+from_unsigned!(u8, u16, u32, u64, u128, usize);
+from_signed!(i8, i16, i32, i64, i128, isize);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer(Repr::Text(text))
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer(Repr::Text(text.to_owned()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn integer_widths_compare_equal() {
+        assert_eq!(Answer::from(5u8), Answer::from(5i64));
+        assert_eq!(Answer::from(-3i32).as_i64(), Some(-3));
+        assert_eq!(Answer::from(-3i32).as_u64(), None);
+        assert_eq!(Answer::from(u128::MAX).as_u64(), None);
+        assert_eq!(Answer::from("ABC").to_string(), "ABC");
+    }
+
+    #[test]
+    fn decimal_digits() {
+        assert_eq!(Answer::from_decimal("0042"), Some(Answer::from(42u8)));
+        assert_eq!(Answer::from_decimal("-0"), Some(Answer::from(0u8)));
+        assert_eq!(
+            Answer::from_decimal(&i128::MIN.to_string()),
+            Some(Answer::from(i128::MIN))
+        );
+        let big = "340282366920938463463374607431768211456";
+        assert_eq!(Answer::from_decimal(big).unwrap().to_string(), big);
+        assert_eq!(Answer::from_decimal(big).unwrap().as_u128(), None);
+        let negative = Answer::from_decimal(&format!("-000{}", big)).unwrap();
+        assert_eq!(negative.to_string(), format!("-{}", big));
+        for not_decimal in ["", "-", "1e5", "+3", "12 "] {
+            assert_eq!(Answer::from_decimal(not_decimal), None, "{:?}", not_decimal);
+        }
+    }
+}
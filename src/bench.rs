@@ -0,0 +1,280 @@
+use anyhow::anyhow;
+use std::{
+    fmt::Display,
+    fs::{read_to_string, write},
+    io::{self, Write},
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use crate::solution::Solution;
+
+pub const DEFAULT_ITERATIONS: usize = 100;
+
+/// A median this much slower than the baseline's is reported as a regression.
+pub const REGRESSION_THRESHOLD: f64 = 0.10;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// Panics if `samples` is empty.
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        let count = sorted.len() as f64;
+        let mean = sorted.iter().map(Duration::as_secs_f64).sum::<f64>() / count;
+        let variance = sorted
+            .iter()
+            .map(|sample| (sample.as_secs_f64() - mean).powi(2))
+            .sum::<f64>()
+            / count;
+        Stats {
+            min: sorted[0],
+            median: sorted[sorted.len() / 2],
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Parse,
+    Solve,
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(match self {
+            Phase::Parse => "parse",
+            Phase::Solve => "solve",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchResult {
+    pub day: u8,
+    pub part: u8,
+    pub iterations: usize,
+    pub parse: Stats,
+    pub solve: Stats,
+}
+
+impl BenchResult {
+    pub fn phase(&self, phase: Phase) -> &Stats {
+        match phase {
+            Phase::Parse => &self.parse,
+            Phase::Solve => &self.solve,
+        }
+    }
+}
+
+/// Parses and solves `part` of `solution` `iterations` times, timing each phase separately.
+pub fn bench(
+    solution: &dyn Solution,
+    part: u8,
+    input: &str,
+    iterations: usize,
+) -> anyhow::Result<BenchResult> {
+    if iterations == 0 {
+        return Err(anyhow!("Must run at least one iteration"));
+    }
+    let mut parse_times = Vec::with_capacity(iterations);
+    let mut solve_times = Vec::with_capacity(iterations);
+    for _ in 0..iterations {
+        let start = Instant::now();
+        let parsed = solution.parse(&mut input.as_bytes())?;
+        parse_times.push(start.elapsed());
+
+        let start = Instant::now();
+        solution.solve(part, &parsed)?;
+        solve_times.push(start.elapsed());
+    }
+    Ok(BenchResult {
+        day: solution.day(),
+        part,
+        iterations,
+        parse: Stats::from_samples(&parse_times),
+        solve: Stats::from_samples(&solve_times),
+    })
+}
+
+/// Saved results, stored one line per day, part and phase as tab-separated nanoseconds:
+/// `day part phase min median mean stddev`.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Baseline {
+    entries: Vec<(u8, u8, Phase, Stats)>,
+}
+
+impl Baseline {
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        read_to_string(path)?.parse()
+    }
+
+    /// Loads the baseline at `path`, or an empty one if the file doesn't exist yet.
+    pub fn load_or_default(path: &Path) -> anyhow::Result<Self> {
+        if path.exists() {
+            Self::load(path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        Ok(write(path, self.to_string())?)
+    }
+
+    pub fn get(&self, day: u8, part: u8, phase: Phase) -> Option<&Stats> {
+        self.entries
+            .iter()
+            .find(|(d, p, ph, _)| *d == day && *p == part && *ph == phase)
+            .map(|(_, _, _, stats)| stats)
+    }
+
+    /// Records `result`, replacing any previous entry for the same day and part.
+    pub fn record(&mut self, result: &BenchResult) {
+        self.entries
+            .retain(|(day, part, _, _)| (*day, *part) != (result.day, result.part));
+        for phase in [Phase::Parse, Phase::Solve] {
+            self.entries
+                .push((result.day, result.part, phase, *result.phase(phase)));
+        }
+        self.entries
+            .sort_by_key(|(day, part, phase, _)| (*day, *part, matches!(phase, Phase::Solve)));
+    }
+}
+
+impl Display for Baseline {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (day, part, phase, stats) in self.entries.iter() {
+            writeln!(
+                f,
+                "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+                day,
+                part,
+                phase,
+                stats.min.as_nanos(),
+                stats.median.as_nanos(),
+                stats.mean.as_nanos(),
+                stats.stddev.as_nanos()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for Baseline {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let entries = s
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                let fields: Vec<&str> = line.split('\t').collect();
+                if fields.len() != 7 {
+                    return Err(anyhow!("Bad baseline line: {}", line));
+                }
+                let phase = match fields[2] {
+                    "parse" => Phase::Parse,
+                    "solve" => Phase::Solve,
+                    other => return Err(anyhow!("Bad phase in baseline: {}", other)),
+                };
+                let nanos = |field: &str| -> anyhow::Result<Duration> {
+                    Ok(Duration::from_nanos(field.parse()?))
+                };
+                let stats = Stats {
+                    min: nanos(fields[3])?,
+                    median: nanos(fields[4])?,
+                    mean: nanos(fields[5])?,
+                    stddev: nanos(fields[6])?,
+                };
+                Ok((fields[0].parse()?, fields[1].parse()?, phase, stats))
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Baseline { entries })
+    }
+}
+
+/// How much slower (positive) or faster (negative) `current` is than `baseline`, by median.
+pub fn median_change(current: &Stats, baseline: &Stats) -> f64 {
+    let baseline = baseline.median.as_secs_f64();
+    if baseline == 0.0 {
+        return 0.0;
+    }
+    (current.median.as_secs_f64() - baseline) / baseline
+}
+
+pub fn write_report(
+    result: &BenchResult,
+    baseline: Option<&Baseline>,
+    out: &mut impl Write,
+) -> io::Result<()> {
+    writeln!(
+        out,
+        "Day {} part {}, {} iterations",
+        result.day, result.part, result.iterations
+    )?;
+    writeln!(
+        out,
+        "{:<5}  {:>12}  {:>12}  {:>12}  {:>12}",
+        "", "min", "median", "mean", "stddev"
+    )?;
+    for phase in [Phase::Parse, Phase::Solve] {
+        let stats = result.phase(phase);
+        write!(
+            out,
+            "{:<5}  {:>12}  {:>12}  {:>12}  {:>12}",
+            phase,
+            format!("{:.2?}", stats.min),
+            format!("{:.2?}", stats.median),
+            format!("{:.2?}", stats.mean),
+            format!("{:.2?}", stats.stddev)
+        )?;
+        if let Some(previous) = baseline.and_then(|b| b.get(result.day, result.part, phase)) {
+            let change = median_change(stats, previous);
+            write!(out, "  {:+.1}% vs baseline", change * 100.0)?;
+            if change > REGRESSION_THRESHOLD {
+                write!(out, "  REGRESSION")?;
+            }
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn baseline_round_trip() {
+        let stats = Stats::from_samples(&[
+            Duration::from_nanos(30),
+            Duration::from_nanos(10),
+            Duration::from_nanos(20),
+        ]);
+        assert_eq!(stats.min, Duration::from_nanos(10));
+        assert_eq!(stats.median, Duration::from_nanos(20));
+
+        let mut baseline = Baseline::default();
+        baseline.record(&BenchResult {
+            day: 9,
+            part: 2,
+            iterations: 3,
+            parse: stats,
+            solve: stats,
+        });
+        let reloaded: Baseline = baseline.to_string().parse().unwrap();
+        assert_eq!(reloaded, baseline);
+        assert_eq!(reloaded.get(9, 2, Phase::Solve), Some(&stats));
+        assert_eq!(reloaded.get(9, 1, Phase::Solve), None);
+    }
+}
@@ -1,22 +1,18 @@
 use std::io::BufRead;
 
-pub fn part1(input: impl BufRead) -> anyhow::Result<String> {
-    let depths: Box<[u32]> = input
-        .lines()
-        .map(|line| Ok(line?.parse()?))
-        .collect::<anyhow::Result<_>>()?;
+use crate::{answer::Answer, parse_error::ParseError};
+
+crate::solution!(1, "Sonar Sweep");
+
+pub fn part1(depths: &Input) -> anyhow::Result<Answer> {
     let increases = depths
         .windows(2)
         .filter(|window| window[0] < window[1])
         .count();
-    Ok(format!("{}", increases))
+    Ok(increases.into())
 }
 
-pub fn part2(input: impl BufRead) -> anyhow::Result<String> {
-    let depths: Box<[u32]> = input
-        .lines()
-        .map(|line| Ok(line?.parse()?))
-        .collect::<anyhow::Result<_>>()?;
+pub fn part2(depths: &Input) -> anyhow::Result<Answer> {
     let window_sums: Box<[u32]> = depths
         .windows(3)
         .map(|window| window.iter().sum())
@@ -25,5 +21,21 @@ pub fn part2(input: impl BufRead) -> anyhow::Result<String> {
         .windows(2)
         .filter(|window| window[0] < window[1])
         .count();
-    Ok(format!("{}", increases))
+    Ok(increases.into())
+}
+
+pub type Input = Box<[u32]>;
+
+pub fn parse_input(input: impl BufRead) -> anyhow::Result<Input> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(line_idx, line)| {
+            let line = line?;
+            let depth = line
+                .parse()
+                .map_err(|_| ParseError::new(&line, "a depth").on_line(line_idx))?;
+            Ok(depth)
+        })
+        .collect::<anyhow::Result<_>>()
 }
@@ -1,42 +1,48 @@
 use anyhow::anyhow;
 use std::{io::BufRead, str::FromStr};
 
-pub fn part1(input: impl BufRead) -> anyhow::Result<String> {
-    let commands: Box<[Command]> = input
-        .lines()
-        .map(|line| line?.parse())
-        .collect::<Result<_, _>>()?;
-    let mut horizontal = 0u32;
-    let mut depth = 0u32;
+use crate::{answer::Answer, parse_error::ParseError};
+
+crate::solution!(2, "Dive!");
+
+// Signed, since nothing stops the input steering the submarine above the surface
+type Distance = i64;
+
+pub fn part1(commands: &Input) -> anyhow::Result<Answer> {
+    let mut horizontal: Distance = 0;
+    let mut depth: Distance = 0;
     for command in commands.iter() {
+        let amount = Distance::from(command.amount);
         match command.direction {
-            Forward => horizontal += command.amount,
-            Down => depth += command.amount,
-            Up => depth -= command.amount,
+            Forward => horizontal = checked(horizontal.checked_add(amount))?,
+            Down => depth = checked(depth.checked_add(amount))?,
+            Up => depth = checked(depth.checked_sub(amount))?,
         }
     }
-    Ok(format!("{}", horizontal * depth))
+    Ok(checked(horizontal.checked_mul(depth))?.into())
 }
 
-pub fn part2(input: impl BufRead) -> anyhow::Result<String> {
-    let commands: Box<[Command]> = input
-        .lines()
-        .map(|line| line?.parse())
-        .collect::<Result<_, _>>()?;
-    let mut horizontal = 0u32;
-    let mut depth = 0u32;
-    let mut aim = 0i32;
+pub fn part2(commands: &Input) -> anyhow::Result<Answer> {
+    let mut horizontal: Distance = 0;
+    let mut depth: Distance = 0;
+    let mut aim: Distance = 0;
     for command in commands.iter() {
+        let amount = Distance::from(command.amount);
         match command.direction {
-            Down => aim += command.amount as i32,
-            Up => aim -= command.amount as i32,
+            Down => aim = checked(aim.checked_add(amount))?,
+            Up => aim = checked(aim.checked_sub(amount))?,
             Forward => {
-                horizontal += command.amount;
-                depth += (command.amount as i32 * aim) as u32;
+                horizontal = checked(horizontal.checked_add(amount))?;
+                depth = checked(amount.checked_mul(aim).and_then(|dive| depth.checked_add(dive)))?;
             }
         }
     }
-    Ok(format!("{}", horizontal * depth))
+    Ok(checked(horizontal.checked_mul(depth))?.into())
+}
+
+/// Turns the result of checked arithmetic into an error if it overflowed
+fn checked(distance: Option<Distance>) -> anyhow::Result<Distance> {
+    distance.ok_or_else(|| anyhow!("The submarine went too far to keep track of"))
 }
 
 enum Direction {
@@ -46,25 +52,43 @@ enum Direction {
 }
 use Direction::*;
 
-struct Command {
+pub struct Command {
     direction: Direction,
     amount: u32,
 }
 
 impl FromStr for Command {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(line: &str) -> Result<Self, Self::Err> {
         let (direction, amount) = line
             .split_once(' ')
-            .ok_or_else(|| anyhow!("Invalid command: {}", line))?;
+            .ok_or_else(|| ParseError::new(line, "a direction and an amount"))?;
         let direction = match direction {
             "forward" => Forward,
             "down" => Down,
             "up" => Up,
-            _ => return Err(anyhow!("Bad direction: {}", direction)),
+            _ => {
+                return Err(ParseError::at(
+                    line,
+                    direction,
+                    "\"forward\", \"down\" or \"up\"",
+                ))
+            }
         };
-        let amount = amount.parse()?;
+        let amount = amount
+            .parse()
+            .map_err(|_| ParseError::at(line, amount, "an amount"))?;
         Ok(Command { direction, amount })
     }
 }
+
+pub type Input = Box<[Command]>;
+
+pub fn parse_input(input: impl BufRead) -> anyhow::Result<Input> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(line_idx, line)| Ok(line?.parse::<Command>().map_err(|err| err.on_line(line_idx))?))
+        .collect()
+}
@@ -1,50 +1,72 @@
 use anyhow::anyhow;
-use std::{io::BufRead, mem::size_of};
+use std::io::BufRead;
 
-pub fn part1(input: impl BufRead) -> anyhow::Result<String> {
-    let (ints, max_bit_depth) = parse_input(input)?;
+use crate::{answer::Answer, parse_error::ParseError};
+
+crate::solution!(3, "Binary Diagnostic");
+
+pub fn part1(input: &Input) -> anyhow::Result<Answer> {
+    let (ints, max_bit_depth) = input;
     let mut bitwise_average = 0;
     for bit_idx in 0..INT_BIT_DEPTH {
-        bitwise_average |= bit_average(&ints, bit_idx);
+        bitwise_average |= bit_average(ints, bit_idx);
     }
-    let anti_average_mask = (1 << max_bit_depth) - 1;
+    let anti_average_mask = ((1u64 << *max_bit_depth) - 1) as IntType;
     let anti_average = bitwise_average ^ anti_average_mask;
-    Ok(format!("{}", bitwise_average * anti_average))
+    Ok((bitwise_average as u64 * anti_average as u64).into())
 }
 
-pub fn part2(input: impl BufRead) -> anyhow::Result<String> {
-    let (ints, max_bit_depth) = parse_input(input)?;
-    let thing1 = bitwise_similarity_filter(&ints, max_bit_depth, false)?;
-    let thing2 = bitwise_similarity_filter(&ints, max_bit_depth, true)?;
-    Ok(format!("{}", thing1 * thing2))
+pub fn part2(input: &Input) -> anyhow::Result<Answer> {
+    let (ints, max_bit_depth) = input;
+    let thing1 = bitwise_similarity_filter(ints, *max_bit_depth, false)?;
+    let thing2 = bitwise_similarity_filter(ints, *max_bit_depth, true)?;
+    Ok((thing1 as u64 * thing2 as u64).into())
 }
 
 type IntType = u32;
-const INT_BIT_DEPTH: usize = size_of::<IntType>() * 8;
+const INT_BIT_DEPTH: usize = IntType::BITS as usize;
+
+/// The parsed numbers, and the length of the longest line
+pub type Input = (Vec<IntType>, usize);
 
-fn parse_input(input: impl BufRead) -> anyhow::Result<(Vec<IntType>, usize)> {
+pub fn parse_input(input: impl BufRead) -> anyhow::Result<Input> {
     let mut max_line_length = 0;
     let ints = input
         .lines()
-        .map(|line| {
+        .enumerate()
+        .map(|(line_idx, line)| {
             let line = line?;
-            let len = line.len();
-            assert!(len <= INT_BIT_DEPTH);
+            let len = line.chars().count();
+            if len > INT_BIT_DEPTH {
+                let expected = format!("at most {} binary digits", INT_BIT_DEPTH);
+                return Err(ParseError::new(&line, expected)
+                    .on_line(line_idx)
+                    .at_column(INT_BIT_DEPTH)
+                    .into());
+            }
             if max_line_length < len {
                 max_line_length = len
             }
             let mut out: IntType = 0;
-            for char_ in line.chars() {
+            for (column_idx, char_) in line.chars().enumerate() {
                 out <<= 1;
                 match char_ {
                     '1' => out |= 1,
                     '0' => {}
-                    _ => return Err(anyhow!("Input line was not binary")),
+                    _ => {
+                        return Err(ParseError::new(&line, "a binary digit")
+                            .on_line(line_idx)
+                            .at_column(column_idx)
+                            .into())
+                    }
                 };
             }
             Ok(out)
         })
-        .collect::<Result<Vec<_>, _>>()?;
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    if ints.is_empty() {
+        return Err(ParseError::new("", "a binary number").into());
+    }
     Ok((ints, max_line_length))
 }
 
@@ -80,5 +102,5 @@ fn bitwise_similarity_filter(
             .filter(|int| anti ^ ((int & mask) ^ bit_average == 0))
             .collect::<Vec<_>>();
     }
-    Ok(*out.get(0).ok_or(anyhow!("Empty"))?)
+    Ok(*out.first().ok_or(anyhow!("Empty"))?)
 }
@@ -1,37 +1,35 @@
 use anyhow::anyhow;
-use std::{
-    fmt::Debug,
-    io::BufRead,
-    mem::{transmute, MaybeUninit},
-};
-
-pub fn part1(input: impl BufRead) -> anyhow::Result<String> {
-    let input = parse_input(input)?;
-    let mut state = input.boards;
-    for drawn_number in input.random_draw.into_iter() {
+use std::{fmt::Debug, io::BufRead};
+
+use crate::{answer::Answer, helpers::matrix::Matrix, parse_error::ParseError};
+
+crate::solution!(4, "Giant Squid");
+
+pub fn part1(input: &Input) -> anyhow::Result<Answer> {
+    let mut state = input.boards.clone();
+    for drawn_number in input.random_draw.iter() {
         for board in state.iter_mut() {
-            board.draw_number(drawn_number);
+            board.draw_number(*drawn_number);
             if let Some(score) = board.score {
-                return Ok(format!("{}", score));
+                return Ok(score.into());
             }
         }
     }
     Err(anyhow!("No winners :("))
 }
 
-pub fn part2(input: impl BufRead) -> anyhow::Result<String> {
-    let input = parse_input(input)?;
-    let mut state = input.boards.into_boxed_slice();
+pub fn part2(input: &Input) -> anyhow::Result<Answer> {
+    let mut state = input.boards.clone().into_boxed_slice();
     let mut last_complete_idx: Option<usize> = None;
-    for drawn_number in input.random_draw.into_iter() {
+    for drawn_number in input.random_draw.iter() {
         for (board_idx, board) in state.iter_mut().enumerate() {
-            if board.draw_number(drawn_number) {
+            if board.draw_number(*drawn_number) {
                 last_complete_idx = Some(board_idx);
             }
         }
     }
     if let Some(idx) = last_complete_idx {
-        Ok(format!("{}", state[idx].score.unwrap()))
+        Ok(state[idx].score.unwrap().into())
     } else {
         Err(anyhow!("No winners :("))
     }
@@ -47,17 +45,16 @@ struct BingoNumber {
 
 const BOARD_HEIGHT: usize = 5;
 const BOARD_WIDTH: usize = 5;
-const BOARD_SIZE: usize = BOARD_WIDTH * BOARD_HEIGHT;
 
 #[derive(Clone)]
 struct Board {
-    numbers: [BingoNumber; BOARD_SIZE],
+    numbers: Matrix<BingoNumber>,
     score: Option<u32>,
 }
 
 impl Debug for Board {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for row in Self::rows(&self.numbers) {
+        for row in self.numbers.rows() {
             for bn in row.iter() {
                 if bn.marked {
                     write!(f, "{:>2}* ", bn.number)?;
@@ -72,11 +69,11 @@ impl Debug for Board {
 }
 
 impl Board {
-    fn new(numbers: [Int; BOARD_SIZE]) -> Self {
+    fn new(numbers: &Matrix<Int>) -> Self {
         Board {
             numbers: numbers.map(|n| BingoNumber {
                 marked: false,
-                number: n,
+                number: *n,
             }),
             score: None,
         }
@@ -88,21 +85,21 @@ impl Board {
             return false;
         }
 
-        for bingo_number in self.numbers.iter_mut() {
+        for bingo_number in self.numbers.value_mut() {
             if bingo_number.number == number {
                 bingo_number.marked = true;
             }
         }
 
-        for row in Self::rows(&self.numbers) {
+        for row in self.numbers.rows() {
             if row.iter().all(|bn| bn.marked) {
                 self.score = Some(number as u32 * self.sum_unmarked());
                 return true;
             }
         }
 
-        for col in Self::cols(&self.numbers) {
-            if col.iter().all(|bn| bn.marked) {
+        for mut col in self.numbers.cols() {
+            if col.all(|bn| bn.marked) {
                 self.score = Some(number as u32 * self.sum_unmarked());
                 return true;
             }
@@ -113,76 +110,79 @@ impl Board {
 
     fn sum_unmarked(&self) -> u32 {
         self.numbers
-            .iter()
+            .values()
             .filter(|bn| !bn.marked)
             .fold(0u32, |total, item| total + item.number as u32)
     }
-
-    fn rows(numbers: &[BingoNumber]) -> impl Iterator<Item = [BingoNumber; BOARD_WIDTH]> + '_ {
-        ({ 0..BOARD_HEIGHT }).map(|row_idx| {
-            let start = row_idx * BOARD_WIDTH;
-            let end = (row_idx + 1) * BOARD_WIDTH;
-            numbers[start..end].try_into().unwrap()
-        })
-    }
-
-    fn cols(numbers: &[BingoNumber]) -> impl Iterator<Item = [BingoNumber; BOARD_HEIGHT]> + '_ {
-        ({ 0..BOARD_WIDTH }).map(|col_idx| {
-            let mut out: [MaybeUninit<BingoNumber>; BOARD_HEIGHT] =
-                unsafe { MaybeUninit::uninit().assume_init() };
-            for (row_idx, item) in out.iter_mut().enumerate() {
-                let _ = *item.write(numbers[row_idx * BOARD_WIDTH + col_idx]);
-            }
-            unsafe { transmute(out) }
-        })
-    }
 }
 
 #[derive(Debug)]
-struct Input {
+pub struct Input {
     random_draw: Vec<Int>,
     boards: Vec<Board>,
 }
 
-fn parse_input(input: impl BufRead) -> anyhow::Result<Input> {
+pub fn parse_input(input: impl BufRead) -> anyhow::Result<Input> {
     let lines = input.lines().collect::<Result<Vec<_>, _>>()?;
-    let mut paras = lines
-        .split(|line| line.is_empty())
-        .map(|lines| lines.join(" "));
-    let random_draw = paras
-        .next()
-        .ok_or(anyhow!("Not enough input"))?
+    let draw_line = lines
+        .first()
+        .ok_or_else(|| ParseError::new("", "a comma-separated list of numbers"))?;
+    let random_draw = draw_line
         .split(',')
-        .map(|n| n.parse::<Int>())
-        .collect::<Result<Vec<_>, _>>()?;
-    let boards = paras
-        .map(|para| -> anyhow::Result<_> {
-            let numbers: [Int; BOARD_SIZE] = para
-                .split(' ')
-                .filter(|word| !word.is_empty())
-                .map(|n| n.parse::<Int>())
-                .collect::<Result<Vec<_>, _>>()?
-                .try_into()
-                .map_err(|_| anyhow!("Board size mismatch"))?;
-            Ok(Board::new(numbers))
+        .map(|n| {
+            n.parse::<Int>()
+                .map_err(|_| ParseError::at(draw_line, n, "a number"))
         })
         .collect::<Result<Vec<_>, _>>()?;
+    let numbered_lines: Vec<(usize, &str)> = lines
+        .iter()
+        .map(String::as_str)
+        .enumerate()
+        .skip(1)
+        .collect();
+    let boards = numbered_lines
+        .split(|(_, line)| line.is_empty())
+        .filter(|rows| !rows.is_empty())
+        .map(parse_board)
+        .collect::<Result<Vec<_>, _>>()?;
     Ok(Input {
         random_draw,
         boards,
     })
 }
 
+/// Parses a board from its rows, given with their line indexes
+fn parse_board(rows: &[(usize, &str)]) -> Result<Board, ParseError> {
+    let (first_line_idx, first_line) = rows[0];
+    let table: Vec<&str> = rows.iter().map(|(_, line)| *line).collect();
+    let matrix = Matrix::<Int>::parse_delimited(&table.join("\n"), None, "a number")
+        .map_err(|err| err.shift_lines(first_line_idx))?;
+    if matrix.num_cols() != BOARD_WIDTH {
+        let expected = format!("{} numbers", BOARD_WIDTH);
+        return Err(ParseError::new(first_line, expected).on_line(first_line_idx));
+    }
+    if matrix.num_rows() != BOARD_HEIGHT {
+        let (line_idx, line) = rows[rows.len().min(BOARD_HEIGHT + 1) - 1];
+        let expected = format!("a board of {} rows", BOARD_HEIGHT);
+        return Err(ParseError::new(line, expected).on_line(line_idx));
+    }
+    Ok(Board::new(&matrix))
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
 
     #[test]
     fn board_cols() {
-        let numbers = ({ 0..(BOARD_SIZE as Int) }).collect::<Vec<_>>();
-        let board = Board::new(numbers.try_into().unwrap());
-        let cols = Board::cols(&board.numbers).collect::<Vec<_>>();
-        assert_eq!(cols[0].map(|bn| bn.number), [0, 5, 10, 15, 20]);
-        assert_eq!(cols[4].map(|bn| bn.number), [4, 9, 14, 19, 24]);
+        let numbers = ({ 0..(BOARD_WIDTH * BOARD_HEIGHT) as Int }).collect::<Vec<_>>();
+        let board = Board::new(&Matrix::from_cells(BOARD_WIDTH, numbers).unwrap());
+        let cols = board
+            .numbers
+            .cols()
+            .map(|col| col.map(|bn| bn.number).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(cols[0], [0, 5, 10, 15, 20]);
+        assert_eq!(cols[4], [4, 9, 14, 19, 24]);
     }
 }
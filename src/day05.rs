@@ -1,49 +1,45 @@
 use anyhow::anyhow;
 use lazy_static::lazy_static;
 use regex::Regex;
-use std::{
-    cmp::{max, min},
-    io::BufRead,
-    iter,
+use std::io::BufRead;
+
+use crate::{
+    answer::Answer,
+    helpers::{sparse_grid::SparseGrid, vector::IVec2},
+    parse_error::ParseError,
 };
 
-use crate::helpers::matrix::{Matrix, Point};
+crate::solution!(5, "Hydrothermal Venture");
 
-pub fn part1(input: impl BufRead) -> anyhow::Result<String> {
-    solution(input, false)
+pub fn part1(lines: &Input) -> anyhow::Result<Answer> {
+    solution(lines, false)
 }
 
-pub fn part2(input: impl BufRead) -> anyhow::Result<String> {
-    solution(input, true)
+pub fn part2(lines: &Input) -> anyhow::Result<Answer> {
+    solution(lines, true)
 }
 
-fn solution(input: impl BufRead, diagonals: bool) -> anyhow::Result<String> {
-    let lines = parse_input(input)?.into_iter().collect::<Vec<_>>();
-    let mut farthest_point: Point = Point(0, 0);
-    for line in lines.iter() {
-        farthest_point.0 = max(max(line.start.0, line.end.0), farthest_point.0);
-        farthest_point.1 = max(max(line.start.1, line.end.1), farthest_point.1);
-    }
-    let mut matrix = Matrix::new(farthest_point.1 + 1, farthest_point.0 + 1, 0u8);
+fn solution(lines: &[Line], diagonals: bool) -> anyhow::Result<Answer> {
+    let mut grid = SparseGrid::new(0u32);
     for line in lines.iter() {
         if diagonals {
-            for point in line.points_with_diagonals() {
-                matrix[point] += 1;
+            for point in line.points_with_diagonals()? {
+                grid[point] += 1;
             }
         } else if line.is_horizontal() || line.is_vertical() {
-            for point in line.points() {
-                matrix[point] += 1;
+            for point in line.points()? {
+                grid[point] += 1;
             }
         }
     }
-    let num_crossing_points = matrix.values().filter(|count| **count >= 2).count();
-    Ok(format!("{}", num_crossing_points))
+    let num_crossing_points = grid.values().filter(|count| **count >= 2).count();
+    Ok(num_crossing_points.into())
 }
 
 #[derive(Debug, Clone, Copy)]
-struct Line {
-    start: Point,
-    end: Point,
+pub struct Line {
+    start: IVec2,
+    end: IVec2,
 }
 
 impl Line {
@@ -55,63 +51,66 @@ impl Line {
         self.start.0 == self.end.0
     }
 
-    fn points(&self) -> Box<dyn Iterator<Item = Point> + '_> {
-        if self.is_vertical() {
-            // Vertical
-            let start_y = min(self.start.1, self.end.1);
-            let end_y = max(self.start.1, self.end.1);
-            Box::new(({ start_y..end_y + 1 }).map(|y| Point(self.start.0, y)))
-        } else if self.is_horizontal() {
-            // Horizontal
-            let start_x = min(self.start.0, self.end.0);
-            let end_x = max(self.start.0, self.end.0);
-            Box::new(({ start_x..end_x + 1 }).map(|x| Point(x, self.start.1)))
-        } else {
-            unimplemented!()
+    /// At 45°
+    fn is_diagonal(&self) -> bool {
+        let distance = self.end - self.start;
+        distance.0.abs() == distance.1.abs()
+    }
+
+    /// Only for horizontal and vertical lines
+    fn points(&self) -> anyhow::Result<impl Iterator<Item = IVec2>> {
+        if !(self.is_horizontal() || self.is_vertical()) {
+            return Err(anyhow!("{:?} is neither horizontal nor vertical", self));
         }
+        Ok(self.walk())
     }
 
-    fn points_with_diagonals(&self) -> impl Iterator<Item = Point> + '_ {
-        let horiz_distance = self.end.0 as isize - self.start.0 as isize;
-        let vert_distance = self.end.1 as isize - self.start.1 as isize;
-        assert!(
-            horiz_distance.abs() == 0
-                || vert_distance.abs() == 0
-                || horiz_distance.abs() == vert_distance.abs()
-        );
-        let x_direction = horiz_distance.signum();
-        let y_direction = vert_distance.signum();
-        let mut point = self.start;
-        iter::from_fn(move || {
-            if point.0 != (self.end.0 as isize + x_direction) as usize
-                || point.1 != (self.end.1 as isize + y_direction) as usize
-            {
-                let out = point;
-                point.0 = (point.0 as isize + x_direction) as usize;
-                point.1 = (point.1 as isize + y_direction) as usize;
-                Some(out)
-            } else {
-                None
-            }
-        })
+    /// For horizontal, vertical and 45° lines
+    fn points_with_diagonals(&self) -> anyhow::Result<impl Iterator<Item = IVec2>> {
+        if !(self.is_horizontal() || self.is_vertical() || self.is_diagonal()) {
+            return Err(anyhow!("{:?} is not at a multiple of 45°", self));
+        }
+        Ok(self.walk())
+    }
+
+    /// Steps from `start` to `end`, which must be in line horizontally, vertically or at 45°
+    fn walk(&self) -> impl Iterator<Item = IVec2> {
+        let distance = self.end - self.start;
+        let step = distance.signum();
+        let start = self.start;
+        (0..=distance.chebyshev() as isize).map(move |idx| start + step * idx)
     }
 }
 
-fn parse_input(input: impl BufRead) -> anyhow::Result<Vec<Line>> {
+pub type Input = Vec<Line>;
+
+pub fn parse_input(input: impl BufRead) -> anyhow::Result<Input> {
     input
         .lines()
-        .map(|line| {
+        .enumerate()
+        .map(|(line_idx, line)| {
             lazy_static! {
-                static ref LINE_FMT: Regex = Regex::new(r"^(\d+),(\d+) -> (\d+),(\d+)$").unwrap();
+                static ref LINE_FMT: Regex =
+                    Regex::new(r"^(-?\d+),(-?\d+) -> (-?\d+),(-?\d+)$").unwrap();
             }
-            if let Some(captures) = LINE_FMT.captures(&line?) {
-                Ok(Line {
-                    start: Point(captures[1].parse()?, captures[2].parse()?),
-                    end: Point(captures[3].parse()?, captures[4].parse()?),
-                })
-            } else {
-                Err(anyhow!("Line did not match regex"))
+            let line = line?;
+            let captures = LINE_FMT
+                .captures(&line)
+                .ok_or_else(|| ParseError::new(&line, "x1,y1 -> x2,y2").on_line(line_idx))?;
+            let coord = |idx: usize| {
+                let text = captures.get(idx).unwrap().as_str();
+                text.parse()
+                    .map_err(|_| ParseError::at(&line, text, "a coordinate").on_line(line_idx))
+            };
+            let parsed = Line {
+                start: IVec2(coord(1)?, coord(2)?),
+                end: IVec2(coord(3)?, coord(4)?),
+            };
+            if !(parsed.is_horizontal() || parsed.is_vertical() || parsed.is_diagonal()) {
+                let expected = "a horizontal, vertical or 45° line";
+                return Err(ParseError::new(&line, expected).on_line(line_idx).into());
             }
+            Ok(parsed)
         })
         .collect()
 }
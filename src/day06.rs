@@ -1,34 +1,49 @@
-use anyhow::anyhow;
 use std::io::BufRead;
 
+use crate::{answer::Answer, parse_error::ParseError};
+
+crate::solution!(6, "Lanternfish");
+
 const DAYS_PART1: u8 = 80;
 const DAYS_PART2: u16 = 256;
 
-pub fn part1(input: impl BufRead) -> anyhow::Result<String> {
-    let mut states = parse_input(input)?;
+/// Timers start at 8 for new fish and count down to 0
+const MAX_TIMER: u8 = 8;
+
+pub fn part1(input: &Input) -> anyhow::Result<Answer> {
+    let mut states = input.clone();
     for _ in 0..DAYS_PART1 {
         iterate(&mut states);
     }
-    Ok(format!("{}", states.len()))
+    Ok(states.len().into())
 }
 
-pub fn part2(input: impl BufRead) -> anyhow::Result<String> {
+pub fn part2(input: &Input) -> anyhow::Result<Answer> {
     use part2::*;
-    let mut states = States::new_from(parse_input(input)?);
+    let mut states = States::new_from(input)?;
     for _ in 0..DAYS_PART2 {
         states.iterate();
     }
-    Ok(format!("{}", states.total()))
+    Ok(states.total().into())
 }
 
-fn parse_input(input: impl BufRead) -> anyhow::Result<Vec<u8>> {
-    Ok(input
+pub type Input = Vec<u8>;
+
+pub fn parse_input(input: impl BufRead) -> anyhow::Result<Input> {
+    let line = input
         .lines()
         .next()
-        .ok_or(anyhow!("Empty input"))??
+        .ok_or_else(|| ParseError::new("", "a comma-separated list of timers"))??;
+    let timers = line
         .split(',')
-        .map(|n| n.parse())
-        .collect::<Result<Vec<_>, _>>()?)
+        .map(|n| {
+            n.parse()
+                .ok()
+                .filter(|timer| *timer <= MAX_TIMER)
+                .ok_or_else(|| ParseError::at(&line, n, format!("a timer from 0 to {}", MAX_TIMER)))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(timers)
 }
 
 fn iterate(states: &mut Vec<u8>) {
@@ -48,6 +63,8 @@ fn iterate(states: &mut Vec<u8>) {
 }
 
 mod part2 {
+    use anyhow::anyhow;
+
     type Count = u64;
 
     const MAX_CYCLE_LENGTH: usize = 9;
@@ -65,13 +82,19 @@ mod part2 {
             }
         }
 
-        pub fn new_from(individuals: Vec<u8>) -> Self {
+        pub fn new_from(individuals: &[u8]) -> anyhow::Result<Self> {
             let mut states = Self::empty();
-            for individual in individuals {
-                assert!((individual as usize) < MAX_CYCLE_LENGTH);
+            for &individual in individuals {
+                if individual as usize >= MAX_CYCLE_LENGTH {
+                    return Err(anyhow!(
+                        "Timer {} is out of range (0 to {})",
+                        individual,
+                        MAX_CYCLE_LENGTH - 1
+                    ));
+                }
                 states.counts[individual as usize] += 1;
             }
-            states
+            Ok(states)
         }
 
         pub fn iterate(&mut self) {
@@ -1,49 +1,56 @@
 use anyhow::anyhow;
 use std::io::BufRead;
 
-pub fn part1(input: impl BufRead) -> anyhow::Result<String> {
-    let input = parse_input(input)?;
-    let minimum_distance = minimum_distance(&input, |a, b| (a - b).abs() as u32).unwrap();
-    Ok(format!("{}", minimum_distance))
+use crate::{answer::Answer, parse_error::ParseError};
+
+crate::solution!(7, "The Treachery of Whales");
+
+pub fn part1(input: &Input) -> anyhow::Result<Answer> {
+    let minimum_distance = minimum_distance(input, |a, b| a.abs_diff(b) as Fuel)?;
+    Ok(minimum_distance.into())
 }
 
-pub fn part2(input: impl BufRead) -> anyhow::Result<String> {
-    let input = parse_input(input)?;
-    let minimum_distance = minimum_distance(&input, |a, b| {
-        let d = (a - b).abs() as u32;
+pub fn part2(input: &Input) -> anyhow::Result<Answer> {
+    let minimum_distance = minimum_distance(input, |a, b| {
+        // Can't overflow, as the distance is less than 2^32
+        let d = a.abs_diff(b) as Fuel;
         d * (d + 1) / 2
-    })
-    .unwrap();
-    Ok(format!("{}", minimum_distance))
+    })?;
+    Ok(minimum_distance.into())
 }
 
-fn minimum_distance(positions: &[Disp], distance: impl Fn(Disp, Disp) -> u32) -> Option<u32> {
-    if positions.is_empty() {
-        return None;
-    }
-    let start = *positions.iter().min().unwrap();
+/// Wide enough for the fuel to move one crab any distance, though not always for a total
+type Fuel = u64;
+
+fn minimum_distance(
+    positions: &[Disp],
+    distance: impl Fn(Disp, Disp) -> Fuel,
+) -> anyhow::Result<Fuel> {
+    let start = *positions.iter().min().ok_or_else(|| anyhow!("No crabs"))?;
     let end = *positions.iter().max().unwrap();
-    let mut minimum_distance = u32::MAX;
-    for alignment_position in start..end + 1 {
-        let total_distance = positions
-            .iter()
-            .map(|pos| distance(*pos, alignment_position))
-            .sum();
-        if total_distance < minimum_distance {
-            minimum_distance = total_distance;
-        }
-    }
-    Some(minimum_distance)
+    // A total too large to count can't be the least, so it's left out
+    (start..=end)
+        .filter_map(|alignment_position| {
+            positions.iter().try_fold(0, |total: Fuel, pos| {
+                total.checked_add(distance(*pos, alignment_position))
+            })
+        })
+        .min()
+        .ok_or_else(|| anyhow!("The fuel needed is too much to count"))
 }
 
 type Disp = i32; // Displacement
 
-fn parse_input(input: impl BufRead) -> anyhow::Result<Vec<Disp>> {
-    input
+pub type Input = Vec<Disp>;
+
+pub fn parse_input(input: impl BufRead) -> anyhow::Result<Input> {
+    let line = input
         .lines()
         .next()
-        .ok_or(anyhow!("Empty input"))??
+        .ok_or_else(|| ParseError::new("", "a comma-separated list of positions"))??;
+    let positions = line
         .split(',')
-        .map(|n| n.parse().map_err(anyhow::Error::from))
-        .collect()
+        .map(|n| n.parse().map_err(|_| ParseError::at(&line, n, "a position")))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(positions)
 }
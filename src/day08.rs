@@ -1,10 +1,14 @@
 use anyhow::anyhow;
+use log::{debug, trace};
 use std::io::BufRead;
 
-pub fn part1(input: impl BufRead) -> anyhow::Result<String> {
+use crate::{answer::Answer, parse_error::ParseError};
+
+crate::solution!(8, "Seven Segment Search");
+
+pub fn part1(input: &Input) -> anyhow::Result<Answer> {
     let mut total = 0u32;
-    for input_row in parse_input(input) {
-        let input_row = input_row?;
+    for input_row in input {
         for output_word in input_row.output.iter() {
             match output_word.len() {
                 2 | 3 | 4 | 7 => total += 1,
@@ -13,31 +17,30 @@ pub fn part1(input: impl BufRead) -> anyhow::Result<String> {
             }
         }
     }
-    Ok(format!("{}", total))
+    Ok(total.into())
 }
 
-pub fn part2(input: impl BufRead) -> anyhow::Result<String> {
+pub fn part2(input: &Input) -> anyhow::Result<Answer> {
     let mut total = 0u32;
-    for input_row in parse_input(input) {
-        let input_row = input_row?;
-        eprintln!("{:?}", input_row);
+    for input_row in input {
+        debug!("{:?}", input_row);
         let mut possibilities = Possibilities::new();
         for observed_word in input_row.observations.iter() {
-            possibilities.update(observed_word);
-            eprintln!("{:?}", possibilities);
+            possibilities.update(observed_word)?;
+            trace!("{:?}", possibilities);
         }
         possibilities.reduce();
-        eprintln!("{:?}", possibilities);
+        debug!("{:?}", possibilities);
         let number = input_row
             .output
             .iter()
             .map(|word| possibilities.decode(word))
-            .fold(Ok(0u32), |number, digit| -> anyhow::Result<_> {
-                Ok(number? * 10 + (digit? as u32))
+            .try_fold(0u32, |number, digit| -> anyhow::Result<_> {
+                Ok(number * 10 + (digit? as u32))
             })?;
         total += number;
     }
-    Ok(format!("{}", total))
+    Ok(total.into())
 }
 
 #[derive(Debug)]
@@ -48,8 +51,8 @@ impl Possibilities {
         Self([0x7F; 7])
     }
 
-    fn update(&mut self, received: &[u8]) {
-        let (mask, anti_mask) = get_attestations(received);
+    fn update(&mut self, received: &[u8]) -> anyhow::Result<()> {
+        let (mask, anti_mask) = get_attestations(received)?;
         for (received_wire, wire_possibilities) in self.0.iter_mut().enumerate() {
             if received.iter().any(|r| *r as usize == received_wire) {
                 *wire_possibilities &= mask;
@@ -57,6 +60,7 @@ impl Possibilities {
                 *wire_possibilities &= anti_mask;
             }
         }
+        Ok(())
     }
 
     fn reduce(&mut self) {
@@ -124,44 +128,61 @@ fn is_single_bit(item: u8) -> bool {
     item != 0 && item & (item - 1) == 0
 }
 
-fn get_attestations(received: &[u8]) -> (u8, u8) {
-    match received.len() {
+fn get_attestations(received: &[u8]) -> anyhow::Result<(u8, u8)> {
+    let attestations = match received.len() {
         2 => (bits(&[2, 5]), bits(&[0, 1, 3, 4, 6])), // digit 1
         3 => (bits(&[0, 2, 5]), bits(&[1, 3, 4, 6])), // digit 7
         4 => (bits(&[1, 2, 3, 5]), bits(&[0, 4, 6])), // digit 4
         5 => (0x7F, bits(&[1, 2, 4, 5])), // digits 2, 3, or 5, which share wires 0, 3, and 6
         6 => (0x7F, bits(&[2, 3, 4])),    // digits 0, 6, or 9, which share wires 0, 2, 5 and 6
         7 => (0x7F, 0x00),                // digit 8
-        _ => panic!("Bad received: {:?}", received),
-    }
+        _ => return Err(anyhow!("Bad received: {:?}", received)),
+    };
+    Ok(attestations)
 }
 
 #[derive(Debug)]
-struct InputRow {
+pub struct InputRow {
     observations: Vec<Vec<u8>>,
     output: Vec<Vec<u8>>,
 }
 
-fn parse_input<'input>(
-    input: impl BufRead + 'input,
-) -> impl Iterator<Item = anyhow::Result<InputRow>> + 'input {
-    input.lines().map(|line| {
-        let line = line?;
-        let line_parts: Vec<&str> = line.split(" | ").take(2).collect::<Vec<_>>();
-        if line_parts.len() != 2 {
-            return Err(anyhow!("Bad line"));
-        }
-        let observations: Vec<Vec<_>> = line_parts[0]
-            .split(' ')
-            .map(|word| word.bytes().map(|b| b - b'a').collect())
-            .collect();
-        let output: Vec<Vec<_>> = line_parts[1]
-            .split(' ')
-            .map(|word| word.bytes().map(|b| b - b'a').collect())
-            .collect();
-        Ok(InputRow {
-            observations,
-            output,
+pub type Input = Vec<InputRow>;
+
+pub fn parse_input(input: impl BufRead) -> anyhow::Result<Input> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(line_idx, line)| {
+            let line = line?;
+            let line_parts: Vec<&str> = line.split(" | ").take(2).collect::<Vec<_>>();
+            if line_parts.len() != 2 {
+                let expected = "signal patterns and output digits separated by \" | \"";
+                return Err(ParseError::new(&line, expected).on_line(line_idx).into());
+            }
+            let words = |part: &str| {
+                part.split(' ')
+                    .map(|word| parse_word(&line, word).map_err(|err| err.on_line(line_idx)))
+                    .collect::<Result<Vec<_>, _>>()
+            };
+            Ok(InputRow {
+                observations: words(line_parts[0])?,
+                output: words(line_parts[1])?,
+            })
+        })
+        .collect()
+}
+
+/// Converts a word of segment letters `a` to `g` into wire numbers 0 to 6
+fn parse_word(line: &str, word: &str) -> Result<Vec<u8>, ParseError> {
+    word.char_indices()
+        .map(|(idx, char_)| match char_ {
+            'a'..='g' => Ok(char_ as u8 - b'a'),
+            _ => Err(ParseError::at(
+                line,
+                &word[idx..idx + char_.len_utf8()],
+                "a segment letter from a to g",
+            )),
         })
-    })
+        .collect()
 }
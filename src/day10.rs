@@ -1,28 +1,56 @@
+use anyhow::anyhow;
 use std::io::BufRead;
 
-pub fn part1(input: impl BufRead) -> anyhow::Result<String> {
-    let mut total_score = 0u32;
-    for line in input.lines() {
-        let validation = validate_line(&line?);
+use crate::{answer::Answer, parse_error::ParseError};
+
+crate::solution!(10, "Syntax Scoring");
+
+pub fn part1(input: &Input) -> anyhow::Result<Answer> {
+    // Even 25137 points a line won't overflow this for any input that fits in memory
+    let mut total_score = 0u64;
+    for line in input {
+        let validation = validate_line(line)?;
         if let Validation::Corrupt(b) = validation {
-            total_score += part1_score(&b);
+            total_score += part1_score(&b)?;
         }
     }
-    Ok(format!("{}", total_score))
+    Ok(total_score.into())
 }
 
-pub fn part2(input: impl BufRead) -> anyhow::Result<String> {
+pub fn part2(input: &Input) -> anyhow::Result<Answer> {
     let mut scores = Vec::new();
-    for line in input.lines() {
-        let validation = validate_line(&line?);
+    for line in input {
+        let validation = validate_line(line)?;
         if let Validation::Incomplete(exp) = validation {
-            let score = part2_score(&exp);
+            let score = part2_score(&exp)?;
             scores.push(score);
         }
     }
     scores.sort_unstable();
+    if scores.is_empty() {
+        return Err(anyhow!("No incomplete lines"));
+    }
     let median = scores[scores.len() / 2];
-    Ok(format!("{}", median))
+    Ok(median.into())
+}
+
+pub type Input = Vec<String>;
+
+pub fn parse_input(input: impl BufRead) -> anyhow::Result<Input> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(line_idx, line)| {
+            let line = line?;
+            if let Some(column_idx) = line.chars().position(|c| handle(&c).is_none()) {
+                return Err(ParseError::new(&line, "a bracket")
+                    .on_line(line_idx)
+                    .at_column(column_idx)
+                    .into());
+            }
+            Ok(line)
+        })
+        .collect()
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -32,8 +60,8 @@ enum Action {
 }
 use Action::*;
 
-fn handle(bracket: &char) -> Action {
-    match bracket {
+fn handle(bracket: &char) -> Option<Action> {
+    let action = match bracket {
         '(' => PushExpectedClosing(')'),
         ')' => VerifyExpectedClosing,
         '[' => PushExpectedClosing(']'),
@@ -42,8 +70,9 @@ fn handle(bracket: &char) -> Action {
         '}' => VerifyExpectedClosing,
         '<' => PushExpectedClosing('>'),
         '>' => VerifyExpectedClosing,
-        _ => panic!("handle() called with invalid char"),
-    }
+        _ => return None,
+    };
+    Some(action)
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -53,50 +82,56 @@ enum Validation {
     Valid,
 }
 
-fn validate_line(line: &str) -> Validation {
+fn validate_line(line: &str) -> anyhow::Result<Validation> {
     let mut expected_closing: Vec<char> = Vec::new();
     for bracket in line.chars() {
-        let action = handle(&bracket);
+        let action = handle(&bracket).ok_or_else(|| anyhow!("Not a bracket: {:?}", bracket))?;
         match action {
             PushExpectedClosing(b) => expected_closing.push(b),
             VerifyExpectedClosing => {
                 if Some(bracket) != expected_closing.pop() {
-                    return Validation::Corrupt(bracket);
+                    return Ok(Validation::Corrupt(bracket));
                 }
             }
         }
     }
     if expected_closing.is_empty() {
-        Validation::Valid
+        Ok(Validation::Valid)
     } else {
-        Validation::Incomplete(expected_closing)
+        Ok(Validation::Incomplete(expected_closing))
     }
 }
 
-fn part1_score(bracket: &char) -> u32 {
-    match bracket {
+fn part1_score(bracket: &char) -> anyhow::Result<u64> {
+    let score = match bracket {
         ')' => 3,
         ']' => 57,
         '}' => 1197,
         '>' => 25137,
-        _ => panic!("part1_score() called with invalid char"),
-    }
+        _ => return Err(anyhow!("part1_score() called with invalid char")),
+    };
+    Ok(score)
 }
 
-fn part2_score(expected_closing: &[char]) -> u64 {
-    let mut total = 0;
+fn part2_score(expected_closing: &[char]) -> anyhow::Result<u64> {
+    let mut total: u64 = 0;
     for bracket in expected_closing.iter().rev() {
-        total *= 5;
-        total += match bracket {
+        let points = match bracket {
             ')' => 1,
             ']' => 2,
             '}' => 3,
             '>' => 4,
-            _ => panic!(
-                "part2_score called with invalid char in expected_closing: {:?}",
-                expected_closing
-            ),
+            _ => {
+                return Err(anyhow!(
+                    "part2_score called with invalid char in expected_closing: {:?}",
+                    expected_closing
+                ))
+            }
         };
+        total = total
+            .checked_mul(5)
+            .and_then(|total| total.checked_add(points))
+            .ok_or_else(|| anyhow!("The score for {} unclosed brackets is too large", expected_closing.len()))?;
     }
-    total
+    Ok(total)
 }
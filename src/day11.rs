@@ -1,73 +1,71 @@
+use anyhow::anyhow;
+use log::{debug, trace};
 use std::io::BufRead;
 
-use crate::helpers::matrix::Matrix;
+use crate::{
+    answer::Answer,
+    helpers::{
+        automaton::{Automaton, Rules},
+        matrix::{Matrix, Point},
+    },
+};
 
-pub fn part1(input: impl BufRead) -> anyhow::Result<String> {
-    let mut matrix = parse_input(input)?;
+crate::solution!(11, "Dumbo Octopus");
+
+pub fn part1(input: &Input) -> anyhow::Result<Answer> {
+    let mut octopuses = Automaton::new(input.clone(), Octopuses);
     let mut total_flashed = 0;
     for _ in 0..100 {
-        total_flashed += iterate_flash_step(&mut matrix);
+        total_flashed += iterate_flash_step(&mut octopuses);
     }
-    Ok(format!("{}", total_flashed))
+    Ok(total_flashed.into())
 }
 
-pub fn part2(input: impl BufRead) -> anyhow::Result<String> {
-    let mut matrix = parse_input(input)?;
-    let mut steps = 0u64;
-    while !matrix.values().all(|v| *v == 0) {
-        iterate_flash_step(&mut matrix);
-        steps += 1;
-    }
-    Ok(format!("{}", steps))
+/// Plenty for any grid to synchronise, which takes a few hundred steps for puzzle inputs
+const MAX_STEPS: usize = 100_000;
+
+pub fn part2(input: &Input) -> anyhow::Result<Answer> {
+    let mut octopuses = Automaton::new(input.clone(), Octopuses);
+    let steps = octopuses
+        .run_until(MAX_STEPS, |matrix| matrix.values().all(|v| *v == 0))
+        .ok_or_else(|| anyhow!("Octopuses didn't all flash together within {} steps", MAX_STEPS))?;
+    Ok(steps.into())
 }
 
-fn parse_input(mut input: impl BufRead) -> anyhow::Result<Matrix<u8>> {
+pub type Input = Matrix<u8>;
+
+pub fn parse_input(mut input: impl BufRead) -> anyhow::Result<Input> {
     let mut input_string = String::new();
     input.read_to_string(&mut input_string)?;
-    Matrix::parse_from_table(&input_string)
+    Ok(Matrix::parse_from_table(&input_string)?)
 }
 
-fn iterate_flash_step(matrix: &mut Matrix<u8>) -> usize {
-    eprintln!("{:?}", OctopusMatrix(&matrix));
-    eprintln!("begin step");
-    // First
-    for value in matrix.value_mut() {
-        *value = value.saturating_add(1);
-    }
+/// Every octopus gains energy each step, and those over 9 flash, giving energy to the ones around
+/// them before dropping back to 0
+struct Octopuses;
 
-    // Then
-    let mut flashed_coords_all = Vec::new();
-    let mut has_flashed = true;
-    while has_flashed {
-        eprintln!("begin round");
-        let mut flashed_coords_this_round = Vec::new();
-        has_flashed = false;
-        for (point, value) in matrix.enumerate() {
-            if !flashed_coords_all.contains(&point) && value > 9 {
-                eprintln!("flash at {:?}", point);
-                has_flashed = true;
-                flashed_coords_this_round.push(point);
-            }
-        }
+impl Rules<u8> for Octopuses {
+    fn step(&self, previous: &Matrix<u8>, point: Point) -> u8 {
+        previous[point].saturating_add(1)
+    }
 
-        for coord in &flashed_coords_this_round {
-            matrix[*coord] = matrix[*coord].saturating_add(1);
-            for surrounding_coord in coord.surrounding_points(true) {
-                if let Some(val) = matrix.get_mut(surrounding_coord) {
-                    *val = val.saturating_add(1);
-                }
-            }
-        }
+    fn fires(&self, value: &u8) -> bool {
+        *value > 9
+    }
 
-        flashed_coords_all.extend_from_slice(&flashed_coords_this_round);
+    fn on_neighbour_fired(&self, value: &mut u8) {
+        *value = value.saturating_add(1);
     }
 
-    // Finally
-    for coord in &flashed_coords_all {
-        matrix[*coord] = 0;
+    fn after_firing(&self, value: &mut u8) {
+        *value = 0;
     }
+}
 
-    flashed_coords_all.len()
+fn iterate_flash_step(octopuses: &mut Automaton<u8, Octopuses>) -> usize {
+    trace!("\n{:?}", OctopusMatrix(octopuses.matrix()));
+    debug!("begin step");
+    octopuses.step()
 }
 
 struct OctopusMatrix<'m>(pub &'m Matrix<u8>);
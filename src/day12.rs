@@ -0,0 +1,126 @@
+use anyhow::anyhow;
+use std::{collections::HashMap, io::BufRead};
+
+use crate::{answer::Answer, parse_error::ParseError};
+
+crate::solution!(12, "Passage Pathing");
+
+pub fn part1(input: &Input) -> anyhow::Result<Answer> {
+    Ok(input.count_paths(false).into())
+}
+
+pub fn part2(input: &Input) -> anyhow::Result<Answer> {
+    Ok(input.count_paths(true).into())
+}
+
+type Cave = usize;
+
+/// Bit set of small caves, by index
+type Visited = u64;
+
+#[derive(Debug)]
+pub struct Input {
+    names: Vec<String>,
+    /// Big caves can be visited any number of times
+    big: Vec<bool>,
+    tunnels: Vec<Vec<Cave>>,
+    start: Cave,
+    end: Cave,
+}
+
+impl Input {
+    /// Counts the paths from start to end that visit small caves at most once, except for one
+    /// small cave visited twice if `may_revisit`. The start and end are only visited once.
+    fn count_paths(&self, may_revisit: bool) -> u64 {
+        let mut counted = HashMap::new();
+        self.count_paths_from(self.start, 1 << self.start, may_revisit, &mut counted)
+    }
+
+    /// Paths from `cave` to the end depend only on where they can still go, so each count is
+    /// worked out once, rather than walking every path.
+    fn count_paths_from(
+        &self,
+        cave: Cave,
+        visited: Visited,
+        may_revisit: bool,
+        counted: &mut HashMap<(Cave, Visited, bool), u64>,
+    ) -> u64 {
+        if cave == self.end {
+            return 1;
+        }
+        if let Some(count) = counted.get(&(cave, visited, may_revisit)) {
+            return *count;
+        }
+        let mut count = 0;
+        for &next in self.tunnels[cave].iter() {
+            if self.big[next] {
+                count += self.count_paths_from(next, visited, may_revisit, counted);
+            } else if visited & (1 << next) == 0 {
+                count += self.count_paths_from(next, visited | 1 << next, may_revisit, counted);
+            } else if may_revisit && next != self.start {
+                count += self.count_paths_from(next, visited, false, counted);
+            }
+        }
+        counted.insert((cave, visited, may_revisit), count);
+        count
+    }
+
+    fn cave(&mut self, name: &str) -> Cave {
+        if let Some(cave) = self.names.iter().position(|known| known == name) {
+            return cave;
+        }
+        self.names.push(name.to_owned());
+        self.big.push(name.chars().all(|c| c.is_ascii_uppercase()));
+        self.tunnels.push(Vec::new());
+        self.names.len() - 1
+    }
+}
+
+pub fn parse_input(input: impl BufRead) -> anyhow::Result<Input> {
+    let mut caves = Input {
+        names: Vec::new(),
+        big: Vec::new(),
+        tunnels: Vec::new(),
+        start: 0,
+        end: 0,
+    };
+    for (line_idx, line) in input.lines().enumerate() {
+        let line = line?;
+        let (a, b) = line
+            .split_once('-')
+            .filter(|(a, b)| is_cave_name(a) && is_cave_name(b))
+            .ok_or_else(|| ParseError::new(&line, "a tunnel like start-A").on_line(line_idx))?;
+        let (a, b) = (caves.cave(a), caves.cave(b));
+        if caves.big[a] && caves.big[b] {
+            // There would be no end to the paths going back and forth
+            return Err(ParseError::new(&line, "a tunnel to a small cave")
+                .on_line(line_idx)
+                .into());
+        }
+        caves.tunnels[a].push(b);
+        caves.tunnels[b].push(a);
+    }
+    if caves.names.len() > Visited::BITS as usize {
+        return Err(anyhow!(
+            "Found {} caves, which is more than the {} supported",
+            caves.names.len(),
+            Visited::BITS
+        ));
+    }
+    let find = |name: &str| {
+        caves
+            .names
+            .iter()
+            .position(|known| known == name)
+            .ok_or_else(|| anyhow!("There is no tunnel to the {} cave", name))
+    };
+    caves.start = find("start")?;
+    caves.end = find("end")?;
+    Ok(caves)
+}
+
+fn is_cave_name(name: &str) -> bool {
+    !name.is_empty()
+        && (name.chars().all(|c| c.is_ascii_uppercase())
+            || name.chars().all(|c| c.is_ascii_lowercase()))
+}
@@ -0,0 +1,145 @@
+use anyhow::anyhow;
+use log::debug;
+use std::{collections::HashSet, io::BufRead};
+
+use crate::{
+    answer::Answer,
+    helpers::{
+        grid::Grid,
+        matrix::{Matrix, Point},
+        ocr,
+    },
+    parse_error::ParseError,
+};
+
+crate::solution!(13, "Transparent Origami");
+
+pub fn part1(input: &Input) -> anyhow::Result<Answer> {
+    let first = input.folds.first().ok_or_else(|| anyhow!("No folds"))?;
+    let paper = input.paper.fold(*first)?;
+    Ok(paper.dots.len().into())
+}
+
+/// The letters on the folded paper, or a drawing of it if they can't be read
+pub fn part2(input: &Input) -> anyhow::Result<Answer> {
+    let paper = input
+        .folds
+        .iter()
+        .try_fold(input.paper.clone(), |paper, fold| paper.fold(*fold))?;
+    let dots = paper
+        .to_matrix()
+        .ok_or_else(|| anyhow!("There are no dots on the paper"))?;
+    let drawing = dots.render(|dot| if dot == Some(&true) { '#' } else { '.' });
+    debug!("\n{}", drawing);
+    match ocr::recognise(&dots) {
+        Some(letters) => Ok(letters.into()),
+        None => Ok(drawing.trim_end().into()),
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Fold {
+    /// Folds the right half over to the left, along a vertical line at this x
+    Left(usize),
+    /// Folds the bottom half up, along a horizontal line at this y
+    Up(usize),
+}
+
+#[derive(Debug, Clone)]
+struct Paper {
+    dots: HashSet<Point>,
+    width: usize,
+    height: usize,
+}
+
+impl Paper {
+    fn fold(&self, fold: Fold) -> anyhow::Result<Paper> {
+        let (line, size) = match fold {
+            Fold::Left(x) => (x, self.width),
+            Fold::Up(y) => (y, self.height),
+        };
+        if size > line * 2 + 1 {
+            return Err(anyhow!("{:?} would fold dots past the edge of the paper", fold));
+        }
+        let mut dots = HashSet::with_capacity(self.dots.len());
+        for dot in self.dots.iter() {
+            let coord = match fold {
+                Fold::Left(_) => dot.0,
+                Fold::Up(_) => dot.1,
+            };
+            if coord == line {
+                return Err(anyhow!("{:?} is on the line of {:?}", dot, fold));
+            }
+            let folded = if coord > line { line * 2 - coord } else { coord };
+            dots.insert(match fold {
+                Fold::Left(_) => Point(folded, dot.1),
+                Fold::Up(_) => Point(dot.0, folded),
+            });
+        }
+        let (width, height) = match fold {
+            Fold::Left(x) => (x, self.height),
+            Fold::Up(y) => (self.width, y),
+        };
+        Ok(Paper {
+            dots,
+            width,
+            height,
+        })
+    }
+
+    /// `None` if the paper has no width, which only happens when it has no dots
+    fn to_matrix(&self) -> Option<Matrix<bool>> {
+        Matrix::from_fn(self.height, self.width, |point| self.dots.contains(&point))
+    }
+}
+
+#[derive(Debug)]
+pub struct Input {
+    paper: Paper,
+    folds: Vec<Fold>,
+}
+
+pub fn parse_input(input: impl BufRead) -> anyhow::Result<Input> {
+    let mut dots = HashSet::new();
+    let mut folds = Vec::new();
+    let mut lines = input.lines().enumerate();
+    for (line_idx, line) in lines.by_ref() {
+        let line = line?;
+        if line.is_empty() {
+            break;
+        }
+        let coord = |text: &str| {
+            text.parse::<usize>()
+                .map_err(|_| ParseError::at(&line, text, "a coordinate").on_line(line_idx))
+        };
+        let (x, y) = line
+            .split_once(',')
+            .ok_or_else(|| ParseError::new(&line, "a dot like 6,10").on_line(line_idx))?;
+        dots.insert(Point(coord(x)?, coord(y)?));
+    }
+    for (line_idx, line) in lines {
+        let line = line?;
+        let instruction = line
+            .strip_prefix("fold along ")
+            .and_then(|fold| fold.split_once('='))
+            .ok_or_else(|| ParseError::new(&line, "fold along x=N or y=N").on_line(line_idx))?;
+        let position = instruction.1.parse().map_err(|_| {
+            ParseError::at(&line, instruction.1, "a fold position").on_line(line_idx)
+        })?;
+        folds.push(match instruction.0 {
+            "x" => Fold::Left(position),
+            "y" => Fold::Up(position),
+            axis => return Err(ParseError::at(&line, axis, "x or y").on_line(line_idx).into()),
+        });
+    }
+    let width = dots.iter().map(|dot| dot.0 + 1).max().unwrap_or(0);
+    let height = dots.iter().map(|dot| dot.1 + 1).max().unwrap_or(0);
+    Ok(Input {
+        paper: Paper {
+            dots,
+            width,
+            height,
+        },
+        folds,
+    })
+}
@@ -0,0 +1,119 @@
+use anyhow::anyhow;
+use std::{collections::HashMap, io::BufRead};
+
+use crate::{answer::Answer, parse_error::ParseError};
+
+crate::solution!(14, "Extended Polymerization");
+
+const STEPS_PART1: usize = 10;
+const STEPS_PART2: usize = 40;
+
+pub fn part1(input: &Input) -> anyhow::Result<Answer> {
+    Ok(element_spread(input, STEPS_PART1)?.into())
+}
+
+pub fn part2(input: &Input) -> anyhow::Result<Answer> {
+    Ok(element_spread(input, STEPS_PART2)?.into())
+}
+
+type Count = u64;
+
+/// The count of the most common element minus the count of the least common one, once the
+/// insertion rules have been applied `steps` times.
+pub fn element_spread(input: &Input, steps: usize) -> anyhow::Result<Count> {
+    let mut polymer = Polymer::new(&input.template);
+    for _ in 0..steps {
+        polymer.iterate(&input.rules)?;
+    }
+    let counts = polymer.element_counts();
+    let most = counts.values().max();
+    let least = counts.values().min();
+    match (most, least) {
+        (Some(most), Some(least)) => Ok(most - least),
+        _ => Err(anyhow!("The template is empty")),
+    }
+}
+
+/// Counts of each pair of neighbouring elements, rather than the polymer itself, which doubles
+/// in length every step
+struct Polymer {
+    pairs: HashMap<(char, char), Count>,
+    /// The last element never changes, and is the only one that isn't first in a pair
+    last: Option<char>,
+}
+
+impl Polymer {
+    fn new(template: &[char]) -> Self {
+        let mut pairs = HashMap::new();
+        for pair in template.windows(2) {
+            *pairs.entry((pair[0], pair[1])).or_default() += 1;
+        }
+        Polymer {
+            pairs,
+            last: template.last().copied(),
+        }
+    }
+
+    fn iterate(&mut self, rules: &HashMap<(char, char), char>) -> anyhow::Result<()> {
+        let mut pairs = HashMap::with_capacity(self.pairs.len());
+        for (&(left, right), &count) in self.pairs.iter() {
+            let added: &[(char, char)] = match rules.get(&(left, right)) {
+                Some(&inserted) => &[(left, inserted), (inserted, right)],
+                None => &[(left, right)],
+            };
+            for pair in added {
+                let total: &mut Count = pairs.entry(*pair).or_default();
+                *total = total
+                    .checked_add(count)
+                    .ok_or_else(|| anyhow!("The polymer is too long to count"))?;
+            }
+        }
+        self.pairs = pairs;
+        Ok(())
+    }
+
+    fn element_counts(&self) -> HashMap<char, Count> {
+        let mut counts = HashMap::new();
+        for (&(first, _), &count) in self.pairs.iter() {
+            *counts.entry(first).or_default() += count;
+        }
+        if let Some(last) = self.last {
+            *counts.entry(last).or_default() += 1;
+        }
+        counts
+    }
+}
+
+#[derive(Debug)]
+pub struct Input {
+    template: Vec<char>,
+    rules: HashMap<(char, char), char>,
+}
+
+pub fn parse_input(input: impl BufRead) -> anyhow::Result<Input> {
+    let mut lines = input.lines().enumerate();
+    let template: Vec<char> = match lines.next() {
+        Some((_, line)) => line?.chars().collect(),
+        None => return Err(ParseError::new("", "a polymer template").into()),
+    };
+    let mut rules = HashMap::new();
+    for (line_idx, line) in lines {
+        let line = line?;
+        if line.is_empty() {
+            continue;
+        }
+        let rule = line.split_once(" -> ").and_then(|(pair, inserted)| {
+            let pair: Vec<char> = pair.chars().collect();
+            let inserted: Vec<char> = inserted.chars().collect();
+            match (pair.as_slice(), inserted.as_slice()) {
+                ([left, right], [inserted]) => Some(((*left, *right), *inserted)),
+                _ => None,
+            }
+        });
+        let (pair, inserted) = rule.ok_or_else(|| {
+            ParseError::new(&line, "an insertion rule like CH -> B").on_line(line_idx)
+        })?;
+        rules.insert(pair, inserted);
+    }
+    Ok(Input { template, rules })
+}
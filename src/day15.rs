@@ -0,0 +1,82 @@
+use anyhow::anyhow;
+use std::io::BufRead;
+
+use crate::{
+    answer::Answer,
+    helpers::{
+        matrix::{Connectivity, Matrix, Point},
+        search::{self, Cost},
+        vector::IVec2,
+    },
+};
+
+crate::solution!(15, "Chiton");
+
+pub fn part1(input: &Input) -> anyhow::Result<Answer> {
+    let goal = Point(input.num_cols() - 1, input.num_rows() - 1);
+    let result = search::matrix_astar(input, Point(0, 0), goal, Connectivity::Four);
+    let risk = result
+        .distance(goal)
+        .ok_or_else(|| anyhow!("No path to {:?}", goal))?;
+    Ok(risk.into())
+}
+
+/// The full map is the input tiled this many times in each direction
+const TILES: usize = 5;
+
+pub fn part2(input: &Input) -> anyhow::Result<Answer> {
+    let map = TiledMap { tile: input };
+    let goal = Point(map.num_cols() - 1, map.num_rows() - 1);
+    // Every step costs at least 1, so the distance left never overestimates the risk left
+    let heuristic = |point: Point| IVec2::from(point).manhattan_distance(goal.into()) as Cost;
+    let result = search::astar(Point(0, 0), goal, |point| map.neighbours(point), heuristic);
+    let risk = result
+        .distance(goal)
+        .ok_or_else(|| anyhow!("No path to {:?}", goal))?;
+    Ok(risk.into())
+}
+
+/// The input repeated [`TILES`] times each way, with the risk going up by one for each tile
+/// right or down, and wrapping round from 9 to 1. Risks are worked out as they're needed.
+struct TiledMap<'m> {
+    tile: &'m Matrix<u8>,
+}
+
+impl TiledMap<'_> {
+    fn num_cols(&self) -> usize {
+        self.tile.num_cols() * TILES
+    }
+
+    fn num_rows(&self) -> usize {
+        self.tile.num_rows() * TILES
+    }
+
+    fn risk(&self, point: Point) -> Cost {
+        let (cols, rows) = (self.tile.num_cols(), self.tile.num_rows());
+        let base = self.tile[Point(point.0 % cols, point.1 % rows)] as usize;
+        let raised = base + point.0 / cols + point.1 / rows;
+        ((raised - 1) % 9 + 1) as Cost
+    }
+
+    fn neighbours(&self, point: Point) -> impl Iterator<Item = (Point, Cost)> + '_ {
+        point
+            .surrounding_points(false)
+            .filter(|neighbour| neighbour.0 < self.num_cols() && neighbour.1 < self.num_rows())
+            .map(|neighbour| (neighbour, self.risk(neighbour)))
+    }
+}
+
+pub type Input = Matrix<u8>;
+
+pub fn parse_input(mut input: impl BufRead) -> anyhow::Result<Input> {
+    let mut input_string = String::new();
+    input.read_to_string(&mut input_string)?;
+    // A risk of 0 would underflow the tiled map's wrap, and let the heuristic overestimate
+    let matrix = Matrix::parse_with(&input_string, "a risk from 1 to 9", |char_| {
+        char_
+            .to_digit(10)
+            .filter(|risk| *risk != 0)
+            .map(|risk| risk as u8)
+    })?;
+    Ok(matrix)
+}
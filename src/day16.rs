@@ -0,0 +1,366 @@
+use anyhow::anyhow;
+use log::debug;
+use std::{fmt::Display, io::BufRead};
+
+use crate::{answer::Answer, parse_error::ParseError};
+
+crate::solution!(16, "Packet Decoder");
+
+pub fn part1(input: &Input) -> anyhow::Result<Answer> {
+    Ok(input.version_sum().into())
+}
+
+pub fn part2(input: &Input) -> anyhow::Result<Answer> {
+    debug!("\n{}", input);
+    Ok(input.evaluate()?.into())
+}
+
+/// Literals can be any number of bits long, so values are kept as wide as possible
+type Value = u128;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Packet {
+    version: u8,
+    payload: Payload,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Payload {
+    Literal(Value),
+    Operator(Operator, Vec<Packet>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    Sum,
+    Product,
+    Minimum,
+    Maximum,
+    GreaterThan,
+    LessThan,
+    EqualTo,
+}
+
+impl Operator {
+    fn from_type_id(type_id: u8) -> Option<Self> {
+        let operator = match type_id {
+            0 => Operator::Sum,
+            1 => Operator::Product,
+            2 => Operator::Minimum,
+            3 => Operator::Maximum,
+            5 => Operator::GreaterThan,
+            6 => Operator::LessThan,
+            7 => Operator::EqualTo,
+            _ => return None,
+        };
+        Some(operator)
+    }
+
+    fn is_comparison(self) -> bool {
+        matches!(
+            self,
+            Operator::GreaterThan | Operator::LessThan | Operator::EqualTo
+        )
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Operator::Sum => "sum",
+            Operator::Product => "product",
+            Operator::Minimum => "min",
+            Operator::Maximum => "max",
+            Operator::GreaterThan => "gt",
+            Operator::LessThan => "lt",
+            Operator::EqualTo => "eq",
+        }
+    }
+}
+
+const LITERAL_TYPE_ID: u8 = 4;
+
+/// Packets are walked recursively, so nesting is limited to keep a hostile transmission from
+/// overflowing the stack. Puzzle inputs nest far less deeply.
+const MAX_DEPTH: usize = 256;
+
+impl Packet {
+    fn version_sum(&self) -> u64 {
+        let children = match &self.payload {
+            Payload::Literal(_) => 0,
+            Payload::Operator(_, children) => children.iter().map(Packet::version_sum).sum(),
+        };
+        self.version as u64 + children
+    }
+
+    fn evaluate(&self) -> anyhow::Result<Value> {
+        let (operator, children) = match &self.payload {
+            Payload::Literal(value) => return Ok(*value),
+            Payload::Operator(operator, children) => (*operator, children),
+        };
+        let values = children
+            .iter()
+            .map(Packet::evaluate)
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let overflow = || anyhow!("The {} of {:?} is too large", operator.name(), values);
+        let value = match operator {
+            Operator::Sum => values
+                .iter()
+                .try_fold(0, |total: Value, value| total.checked_add(*value))
+                .ok_or_else(overflow)?,
+            Operator::Product => values
+                .iter()
+                .try_fold(1, |total: Value, value| total.checked_mul(*value))
+                .ok_or_else(overflow)?,
+            Operator::Minimum => *values.iter().min().unwrap(),
+            Operator::Maximum => *values.iter().max().unwrap(),
+            Operator::GreaterThan => (values[0] > values[1]) as Value,
+            Operator::LessThan => (values[0] < values[1]) as Value,
+            Operator::EqualTo => (values[0] == values[1]) as Value,
+        };
+        Ok(value)
+    }
+
+    fn write_indented(&self, f: &mut std::fmt::Formatter<'_>, depth: usize) -> std::fmt::Result {
+        let indent = depth * 2;
+        match &self.payload {
+            Payload::Literal(value) => writeln!(f, "{:indent$}{} [v{}]", "", value, self.version),
+            Payload::Operator(operator, children) => {
+                writeln!(f, "{:indent$}{} [v{}] (", "", operator.name(), self.version)?;
+                for child in children {
+                    child.write_indented(f, depth + 1)?;
+                }
+                writeln!(f, "{:indent$})", "")
+            }
+        }
+    }
+}
+
+/// Shows the packet tree as an expression, one packet per line, indented by depth, with each
+/// packet's version in brackets.
+impl Display for Packet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.write_indented(f, 0)
+    }
+}
+
+/// Reads a transmission bit by bit, most significant first
+struct Bits<'t> {
+    /// The transmission in hex, for pointing at problems in it
+    line: &'t str,
+    bytes: &'t [u8],
+    /// Position of the next bit
+    pos: usize,
+}
+
+impl Bits<'_> {
+    /// An error at the hex digit holding `bit`
+    fn error(&self, bit: usize, expected: impl Into<String>) -> anyhow::Error {
+        ParseError::new(self.line, expected)
+            .at_column(bit / 4)
+            .into()
+    }
+
+    fn read(&mut self, num_bits: usize) -> anyhow::Result<u64> {
+        let mut out = 0;
+        for _ in 0..num_bits {
+            let byte = self
+                .bytes
+                .get(self.pos / 8)
+                .ok_or_else(|| self.error(self.pos, "the rest of the packet"))?;
+            let bit = (byte >> (7 - self.pos % 8)) & 1;
+            out = out << 1 | bit as u64;
+            self.pos += 1;
+        }
+        Ok(out)
+    }
+
+    /// Reads a packet nested `depth` packets deep
+    fn packet(&mut self, depth: usize) -> anyhow::Result<Packet> {
+        let start = self.pos;
+        if depth > MAX_DEPTH {
+            let expected = format!("packets nested at most {} deep", MAX_DEPTH);
+            return Err(self.error(start, expected));
+        }
+        let version = self.read(3)? as u8;
+        let type_id = self.read(3)? as u8;
+        if type_id == LITERAL_TYPE_ID {
+            return Ok(Packet {
+                version,
+                payload: Payload::Literal(self.literal(start)?),
+            });
+        }
+        let operator = Operator::from_type_id(type_id).unwrap();
+        let mut children = Vec::new();
+        if self.read(1)? == 0 {
+            // Children take up a number of bits
+            let length = self.read(15)? as usize;
+            let end = self.pos + length;
+            while self.pos < end {
+                children.push(self.packet(depth + 1)?);
+            }
+            if self.pos != end {
+                return Err(self.error(end, "the packet's children to end here"));
+            }
+        } else {
+            let num_children = self.read(11)?;
+            for _ in 0..num_children {
+                children.push(self.packet(depth + 1)?);
+            }
+        }
+        let expected = if operator.is_comparison() { 2..=2 } else { 1..=usize::MAX };
+        if !expected.contains(&children.len()) {
+            let expected = if operator.is_comparison() { "two" } else { "at least one" };
+            let expected = format!("a {} packet with {} children", operator.name(), expected);
+            return Err(self.error(start, expected));
+        }
+        Ok(Packet {
+            version,
+            payload: Payload::Operator(operator, children),
+        })
+    }
+
+    /// Reads 4 bits at a time, for as long as the bit before them is set
+    fn literal(&mut self, start: usize) -> anyhow::Result<Value> {
+        let mut value: Value = 0;
+        loop {
+            let more = self.read(1)? == 1;
+            if value.leading_zeros() < 4 {
+                return Err(self.error(start, "a literal that fits in 128 bits"));
+            }
+            value = value << 4 | self.read(4)? as Value;
+            if !more {
+                return Ok(value);
+            }
+        }
+    }
+}
+
+pub type Input = Packet;
+
+pub fn parse_input(input: impl BufRead) -> anyhow::Result<Input> {
+    let line = input
+        .lines()
+        .next()
+        .ok_or_else(|| ParseError::new("", "a hexadecimal transmission"))??;
+    let digits = line
+        .chars()
+        .enumerate()
+        .map(|(col_idx, c)| {
+            c.to_digit(16)
+                .map(|digit| digit as u8)
+                .ok_or_else(|| ParseError::new(&line, "a hexadecimal digit").at_column(col_idx))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let bytes: Vec<u8> = digits
+        .chunks(2)
+        .map(|pair| pair[0] << 4 | pair.get(1).copied().unwrap_or(0))
+        .collect();
+    Bits {
+        line: &line,
+        bytes: &bytes,
+        pos: 0,
+    }
+    .packet(0)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn decode(hex: &str) -> Packet {
+        parse_input(hex.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn packet_structure() {
+        assert_eq!(decode("D2FE28").payload, Payload::Literal(2021));
+        let literals = |packet: Packet| match packet.payload {
+            Payload::Operator(_, children) => children
+                .into_iter()
+                .map(|child| child.payload)
+                .collect::<Vec<_>>(),
+            Payload::Literal(_) => panic!("not an operator"),
+        };
+        assert_eq!(
+            literals(decode("38006F45291200")),
+            [Payload::Literal(10), Payload::Literal(20)]
+        );
+        assert_eq!(
+            literals(decode("EE00D40C823060")),
+            [1, 2, 3].map(Payload::Literal)
+        );
+    }
+
+    #[test]
+    fn version_sums() {
+        for (hex, sum) in [
+            ("8A004A801A8002F478", 16),
+            ("620080001611562C8802118E34", 12),
+            ("C0015000016115A2E0802F182340", 23),
+            ("A0016C880162017C3686B18A3D4780", 31),
+        ] {
+            assert_eq!(decode(hex).version_sum(), sum, "{}", hex);
+        }
+    }
+
+    #[test]
+    fn evaluation() {
+        for (hex, value) in [
+            ("C200B40A82", 3),
+            ("04005AC33890", 54),
+            ("880086C3E88112", 7),
+            ("CE00C43D881120", 9),
+            ("D8005AC2A8F0", 1),
+            ("F600BC2D8F", 0),
+            ("9C005AC2F8F0", 0),
+            ("9C0141080250320F1802104A08", 1),
+        ] {
+            assert_eq!(decode(hex).evaluate().unwrap(), value, "{}", hex);
+        }
+    }
+
+    #[test]
+    fn pretty_printing() {
+        assert_eq!(
+            decode("9C0141080250320F1802104A08").to_string(),
+            "eq [v4] (\n  sum [v2] (\n    1 [v2]\n    3 [v4]\n  )\n  \
+             product [v6] (\n    2 [v0]\n    2 [v2]\n  )\n)\n"
+        );
+    }
+
+    #[test]
+    fn decode_errors() {
+        let error = |hex: &str| parse_input(hex.as_bytes()).unwrap_err().to_string();
+        assert_eq!(
+            error("D2XE"),
+            "line 1, column 3: expected a hexadecimal digit, found \"X\""
+        );
+        assert_eq!(
+            error("D2FE"),
+            "line 1, column 5: expected the rest of the packet, found \"\""
+        );
+        // A sum with 11 bits of children, holding a 16-bit literal
+        assert_eq!(
+            error("00002C4884"),
+            "line 1, column 9: expected the packet's children to end here, found \"8\""
+        );
+    }
+
+    #[test]
+    fn nesting_limit() {
+        // Sums each holding one packet, around a literal 1
+        let nested = |depth: usize| {
+            let mut bits = "000000100000000001".repeat(depth) + "00010000001";
+            while !bits.len().is_multiple_of(4) {
+                bits.push('0');
+            }
+            (0..bits.len())
+                .step_by(4)
+                .map(|idx| format!("{:X}", u8::from_str_radix(&bits[idx..idx + 4], 2).unwrap()))
+                .collect::<String>()
+        };
+        assert_eq!(decode(&nested(MAX_DEPTH)).evaluate().unwrap(), 1);
+        let err = parse_input(nested(MAX_DEPTH + 1).as_bytes()).unwrap_err();
+        let expected = format!("expected packets nested at most {} deep", MAX_DEPTH);
+        assert!(err.to_string().contains(&expected), "{}", err);
+    }
+}
@@ -0,0 +1,202 @@
+use log::trace;
+use std::{collections::HashMap, hash::Hash, mem::swap};
+
+use super::matrix::{Connectivity, Matrix, Point};
+
+/// The rules of a cellular automaton over a [`Matrix`].
+///
+/// Each generation, every cell first gets a new value from [`step`](Self::step), based on the
+/// previous generation. Then cells that [`fire`](Self::fires) set off their neighbours, which
+/// may fire in turn, with each cell firing at most once per generation.
+pub trait Rules<T> {
+    /// The new value of the cell at `point`, given the previous generation.
+    fn step(&self, previous: &Matrix<T>, point: Point) -> T;
+
+    /// Whether a cell with this value fires. Nothing fires by default.
+    fn fires(&self, _value: &T) -> bool {
+        false
+    }
+
+    /// Changes a cell next to one that fired.
+    fn on_neighbour_fired(&self, _value: &mut T) {}
+
+    /// Changes a cell that fired, once nothing else is going to.
+    fn after_firing(&self, _value: &mut T) {}
+
+    /// Which cells count as neighbours when firing
+    fn connectivity(&self) -> Connectivity {
+        Connectivity::Eight
+    }
+}
+
+/// A repeating run of generations, found by [`Automaton::find_cycle`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// The first generation in the cycle
+    pub start: usize,
+    pub length: usize,
+}
+
+/// Runs `rules` over a matrix, one generation at a time.
+pub struct Automaton<T, R> {
+    rules: R,
+    current: Matrix<T>,
+    /// Where the next generation is built, so it doesn't need allocating every step
+    next: Matrix<T>,
+    fired: Matrix<bool>,
+    steps: usize,
+}
+
+impl<T: Clone, R: Rules<T>> Automaton<T, R> {
+    pub fn new(matrix: Matrix<T>, rules: R) -> Self {
+        Automaton {
+            rules,
+            next: matrix.clone(),
+            fired: matrix.map(|_| false),
+            current: matrix,
+            steps: 0,
+        }
+    }
+
+    pub fn matrix(&self) -> &Matrix<T> {
+        &self.current
+    }
+
+    /// The number of generations run so far
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Runs one generation, returning the number of cells that fired.
+    pub fn step(&mut self) -> usize {
+        for point in self.fired.points() {
+            self.next[point] = self.rules.step(&self.current, point);
+        }
+        swap(&mut self.current, &mut self.next);
+        self.steps += 1;
+        self.cascade()
+    }
+
+    fn cascade(&mut self) -> usize {
+        for fired in self.fired.value_mut() {
+            *fired = false;
+        }
+        let mut to_fire: Vec<Point> = self
+            .current
+            .points()
+            .filter(|point| self.rules.fires(&self.current[*point]))
+            .collect();
+        for point in to_fire.iter() {
+            self.fired[*point] = true;
+        }
+
+        let mut num_fired = 0;
+        let (rules, current, fired) = (&self.rules, &mut self.current, &mut self.fired);
+        while let Some(point) = to_fire.pop() {
+            trace!("{:?} fired", point);
+            num_fired += 1;
+            current.for_each_neighbour_mut(point, rules.connectivity(), |neighbour, value| {
+                rules.on_neighbour_fired(value);
+                if !fired[neighbour] && rules.fires(value) {
+                    fired[neighbour] = true;
+                    to_fire.push(neighbour);
+                }
+            });
+        }
+
+        for (point, fired) in self.fired.enumerate() {
+            if fired {
+                self.rules.after_firing(&mut self.current[point]);
+            }
+        }
+        num_fired
+    }
+
+    /// Runs generations until `done` holds for the matrix, checking before each one. Returns the
+    /// number of generations run by then, or `None` if it didn't happen within `limit` of them.
+    pub fn run_until(
+        &mut self,
+        limit: usize,
+        mut done: impl FnMut(&Matrix<T>) -> bool,
+    ) -> Option<usize> {
+        let last = self.steps + limit;
+        while !done(&self.current) {
+            if self.steps == last {
+                return None;
+            }
+            self.step();
+        }
+        Some(self.steps)
+    }
+}
+
+impl<T: Clone + PartialEq, R: Rules<T>> Automaton<T, R> {
+    /// Runs generations until one changes nothing, returning the number run by then, or `None`
+    /// if that doesn't happen within `limit` generations.
+    pub fn run_until_stable(&mut self, limit: usize) -> Option<usize> {
+        for _ in 0..limit {
+            self.step();
+            if self.current == self.next {
+                return Some(self.steps);
+            }
+        }
+        None
+    }
+}
+
+impl<T: Clone + Eq + Hash, R: Rules<T>> Automaton<T, R> {
+    /// Runs generations until one repeats an earlier one, giving up after `limit` of them.
+    pub fn find_cycle(&mut self, limit: usize) -> Option<Cycle> {
+        let mut seen = HashMap::from([(self.current.clone(), self.steps)]);
+        for _ in 0..limit {
+            self.step();
+            if let Some(start) = seen.insert(self.current.clone(), self.steps) {
+                return Some(Cycle {
+                    start,
+                    length: self.steps - start,
+                });
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Conway's game of life
+    struct Life;
+
+    impl Rules<bool> for Life {
+        fn step(&self, previous: &Matrix<bool>, point: Point) -> bool {
+            let alive = previous
+                .neighbours(point, Connectivity::Eight)
+                .filter(|(_, alive)| **alive)
+                .count();
+            alive == 3 || (alive == 2 && previous[point])
+        }
+    }
+
+    fn life(table: &str) -> Automaton<bool, Life> {
+        let matrix = Matrix::parse_with(table, "# or .", |c| Some(c == '#')).unwrap();
+        Automaton::new(matrix, Life)
+    }
+
+    #[test]
+    fn stable_and_cyclic_patterns() {
+        let mut block = life("....\n.##.\n.##.\n....");
+        assert_eq!(block.run_until_stable(10), Some(1));
+
+        let mut blinker = life(".....\n..#..\n..#..\n..#..\n.....");
+        assert_eq!(blinker.run_until_stable(10), None);
+        assert_eq!(
+            blinker.find_cycle(10),
+            Some(Cycle {
+                start: 10,
+                length: 2
+            })
+        );
+        assert_eq!(blinker.run_until(5, |matrix| matrix[Point(1, 2)]), Some(13));
+    }
+}
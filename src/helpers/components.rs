@@ -0,0 +1,99 @@
+use super::{
+    disjoint_set::DisjointSet,
+    matrix::{Connectivity, Matrix, Point},
+    vector::IVec2,
+};
+
+/// The connected regions of a matrix, numbered from 0 in the order their first cell appears,
+/// reading row by row.
+#[derive(Clone)]
+pub struct Components {
+    labels: Matrix<Option<usize>>,
+    regions: Vec<Vec<Point>>,
+}
+
+impl Components {
+    /// The region `point` is in, or `None` if it isn't in one.
+    pub fn label(&self, point: Point) -> Option<usize> {
+        self.labels.get(point).copied().flatten()
+    }
+
+    pub fn num_regions(&self) -> usize {
+        self.regions.len()
+    }
+
+    /// The cells in region `label`, row by row
+    pub fn members(&self, label: usize) -> &[Point] {
+        &self.regions[label]
+    }
+
+    pub fn regions(&self) -> impl Iterator<Item = &[Point]> + '_ {
+        self.regions.iter().map(Vec::as_slice)
+    }
+
+    pub fn sizes(&self) -> impl Iterator<Item = usize> + '_ {
+        self.regions.iter().map(Vec::len)
+    }
+}
+
+/// Groups the cells of `matrix` matching `include` into regions of neighbouring cells.
+pub fn label_components<T>(
+    matrix: &Matrix<T>,
+    connectivity: Connectivity,
+    mut include: impl FnMut(&T) -> bool,
+) -> Components {
+    let cols = matrix.num_cols();
+    let included = matrix.map(|value| include(value));
+    let mut set = DisjointSet::new(cols * matrix.num_rows());
+    let index = |point: Point| point.1 * cols + point.0;
+
+    // Neighbours already visited in reading order are enough to join every region up
+    let earlier = match connectivity {
+        Connectivity::Four => &[IVec2::LEFT, IVec2::UP][..],
+        Connectivity::Eight => &[IVec2::LEFT, IVec2(-1, -1), IVec2::UP, IVec2(1, -1)][..],
+    };
+    for (point, _) in included.enumerate().filter(|(_, is_included)| *is_included) {
+        for offset in earlier {
+            let neighbour = point.offset(*offset);
+            if let Some(neighbour) = neighbour.filter(|n| included.get(*n) == Some(&true)) {
+                set.union(index(point), index(neighbour));
+            }
+        }
+    }
+
+    let mut root_labels = vec![None; set.len()];
+    let mut regions: Vec<Vec<Point>> = Vec::new();
+    let labels = Matrix::from_fn(matrix.num_rows(), cols, |point| {
+        if !included[point] {
+            return None;
+        }
+        let root = set.find(index(point));
+        let label = *root_labels[root].get_or_insert_with(|| {
+            regions.push(Vec::new());
+            regions.len() - 1
+        });
+        regions[label].push(point);
+        Some(label)
+    })
+    .expect("The matrix has columns, so the labels do too");
+    Components { labels, regions }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn labels_regions() {
+        let matrix = Matrix::parse_from_table("2199\n3919\n9899").unwrap();
+        let four = label_components(&matrix, Connectivity::Four, |value| *value < 9);
+        assert_eq!(four.sizes().collect::<Vec<_>>(), [3, 1, 1]);
+        assert_eq!(four.label(Point(1, 0)), Some(0));
+        assert_eq!(four.label(Point(2, 1)), Some(1));
+        assert_eq!(four.label(Point(3, 0)), None);
+        assert_eq!(four.members(2), [Point(1, 2)]);
+
+        let eight = label_components(&matrix, Connectivity::Eight, |value| *value < 9);
+        assert_eq!(eight.sizes().collect::<Vec<_>>(), [5]);
+    }
+}
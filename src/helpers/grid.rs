@@ -0,0 +1,27 @@
+use super::vector::IVec2;
+
+/// Storage for a 2D grid of cells, dense or sparse, so solutions can switch between the two.
+pub trait Grid<T> {
+    /// The value at `point`, or `None` if it is outside the grid.
+    fn at(&self, point: IVec2) -> Option<&T>;
+
+    /// The top left and bottom right corners, inclusive, of the area holding values. `None` if
+    /// there are none.
+    fn bounds(&self) -> Option<(IVec2, IVec2)>;
+
+    /// Draws the area within [`bounds`](Self::bounds) with one character per cell, given by
+    /// `cell`, and a line per row.
+    fn render(&self, mut cell: impl FnMut(Option<&T>) -> char) -> String
+    where
+        Self: Sized,
+    {
+        let mut out = String::new();
+        if let Some((min, max)) = self.bounds() {
+            for y in min.1..=max.1 {
+                out.extend((min.0..=max.0).map(|x| cell(self.at(IVec2(x, y)))));
+                out.push('\n');
+            }
+        }
+        out
+    }
+}
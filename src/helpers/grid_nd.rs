@@ -0,0 +1,207 @@
+use std::ops::{Index, IndexMut};
+
+/// A position in an `N`-dimensional [`GridN`], like [`Point`](super::matrix::Point) in 2D
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PointN<const N: usize>(pub [usize; N]);
+
+pub type Point3 = PointN<3>;
+pub type Point4 = PointN<4>;
+
+impl<const N: usize> PointN<N> {
+    /// Moves by `offset`, or returns `None` if that would make any coordinate negative.
+    pub fn offset(&self, offset: [isize; N]) -> Option<Self> {
+        let mut out = *self;
+        for (coord, delta) in out.0.iter_mut().zip(offset) {
+            *coord = coord.checked_add_signed(delta)?;
+        }
+        Some(out)
+    }
+}
+
+/// Which cells count as neighbours of a cell in `N` dimensions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Neighbourhood {
+    /// Cells sharing a face, differing by one along a single axis: 6 in 3D, 8 in 4D
+    Faces,
+    /// Every cell touching, even at a corner: 26 in 3D, 80 in 4D
+    Full,
+}
+
+impl Neighbourhood {
+    /// The offsets to each neighbour, worked out as they're needed rather than stored
+    pub fn offsets<const N: usize>(self) -> impl Iterator<Item = [isize; N]> {
+        let (count, full) = match self {
+            Neighbourhood::Faces => (2 * N, false),
+            Neighbourhood::Full => (3usize.pow(N as u32), true),
+        };
+        (0..count).filter_map(move |idx| {
+            let mut offset = [0; N];
+            if full {
+                // Each axis is a base 3 digit of the index, for -1, 0 or 1
+                let mut rest = idx;
+                for delta in offset.iter_mut() {
+                    *delta = (rest % 3) as isize - 1;
+                    rest /= 3;
+                }
+                offset.iter().any(|delta| *delta != 0).then_some(offset)
+            } else {
+                offset[idx / 2] = if idx % 2 == 0 { -1 } else { 1 };
+                Some(offset)
+            }
+        })
+    }
+}
+
+/// A dense grid in `N` dimensions, like [`Matrix`](super::matrix::Matrix) in 2D.
+///
+/// Cells are stored with the first axis varying fastest.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct GridN<T, const N: usize> {
+    dims: [usize; N],
+    data: Box<[T]>,
+}
+
+pub type Grid3<T> = GridN<T, 3>;
+pub type Grid4<T> = GridN<T, 4>;
+
+impl<T, const N: usize> GridN<T, N> {
+    /// Builds a grid by calling `f` with the position of each cell.
+    pub fn from_fn(dims: [usize; N], mut f: impl FnMut(PointN<N>) -> T) -> Self {
+        let len = dims.iter().product();
+        let data = (0..len).map(|idx| f(Self::point_of(dims, idx))).collect();
+        GridN { dims, data }
+    }
+
+    /// The size along each axis
+    pub fn dims(&self) -> [usize; N] {
+        self.dims
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> + '_ {
+        self.data.iter()
+    }
+
+    pub fn value_mut(&mut self) -> impl Iterator<Item = &mut T> + '_ {
+        self.data.iter_mut()
+    }
+
+    /// Every position in the grid, in storage order
+    pub fn points(&self) -> impl Iterator<Item = PointN<N>> {
+        let dims = self.dims;
+        (0..self.len()).map(move |idx| Self::point_of(dims, idx))
+    }
+
+    pub fn enumerate(&self) -> impl Iterator<Item = (PointN<N>, &T)> + '_ {
+        self.points().zip(self.data.iter())
+    }
+
+    pub fn get(&self, point: PointN<N>) -> Option<&T> {
+        self.data.get(self.index_of(point)?)
+    }
+
+    pub fn get_mut(&mut self, point: PointN<N>) -> Option<&mut T> {
+        let index = self.index_of(point)?;
+        self.data.get_mut(index)
+    }
+
+    /// The cells next to `point` that are inside the grid, with their values.
+    pub fn neighbours(
+        &self,
+        point: PointN<N>,
+        neighbourhood: Neighbourhood,
+    ) -> impl Iterator<Item = (PointN<N>, &T)> + '_ {
+        neighbourhood.offsets().filter_map(move |offset| {
+            let neighbour = point.offset(offset)?;
+            Some((neighbour, self.get(neighbour)?))
+        })
+    }
+
+    fn index_of(&self, point: PointN<N>) -> Option<usize> {
+        let mut index = 0;
+        for (coord, dim) in point.0.iter().zip(self.dims).rev() {
+            if *coord >= dim {
+                return None;
+            }
+            index = index * dim + coord;
+        }
+        Some(index)
+    }
+
+    fn point_of(dims: [usize; N], mut index: usize) -> PointN<N> {
+        let mut point = [0; N];
+        for (coord, dim) in point.iter_mut().zip(dims) {
+            *coord = index % dim;
+            index /= dim;
+        }
+        PointN(point)
+    }
+}
+
+impl<T: Clone, const N: usize> GridN<T, N> {
+    pub fn new(dims: [usize; N], fill: T) -> Self {
+        Self::from_fn(dims, |_| fill.clone())
+    }
+}
+
+impl<T, const N: usize> Index<PointN<N>> for GridN<T, N> {
+    type Output = T;
+
+    fn index(&self, index: PointN<N>) -> &Self::Output {
+        self.get(index).unwrap()
+    }
+}
+
+impl<T, const N: usize> IndexMut<PointN<N>> for GridN<T, N> {
+    fn index_mut(&mut self, index: PointN<N>) -> &mut Self::Output {
+        self.get_mut(index).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::helpers::search;
+
+    #[test]
+    fn neighbour_counts() {
+        let grid = Grid3::new([3, 3, 3], 0u8);
+        let centre = PointN([1, 1, 1]);
+        assert_eq!(grid.neighbours(centre, Neighbourhood::Faces).count(), 6);
+        assert_eq!(grid.neighbours(centre, Neighbourhood::Full).count(), 26);
+        assert_eq!(
+            grid.neighbours(PointN([0, 0, 2]), Neighbourhood::Full)
+                .count(),
+            7
+        );
+        let grid = Grid4::new([3, 3, 3, 3], 0u8);
+        assert_eq!(
+            grid.neighbours(PointN([1, 1, 1, 1]), Neighbourhood::Full)
+                .count(),
+            80
+        );
+        assert_eq!(grid.points().nth(4), Some(PointN([1, 1, 0, 0])));
+    }
+
+    #[test]
+    fn voxel_flood_fill() {
+        // A hollow 3x3x3 cube of walls with one open cell inside
+        let grid = Grid3::from_fn([5, 5, 5], |point| {
+            point.0.iter().all(|coord| (1..=3).contains(coord)) && point != PointN([2, 2, 2])
+        });
+        let outside = search::bfs(PointN([0, 0, 0]), |point| {
+            grid.neighbours(point, Neighbourhood::Faces)
+                .filter(|(_, wall)| !**wall)
+                .map(|(neighbour, _)| neighbour)
+        });
+        assert_eq!(outside.num_reached(), 5 * 5 * 5 - 27);
+        assert_eq!(outside.distance(PointN([2, 2, 2])), None);
+    }
+}
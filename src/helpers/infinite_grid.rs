@@ -0,0 +1,165 @@
+use std::ops::{Index, IndexMut};
+
+use super::{
+    grid::Grid,
+    matrix::{Matrix, Point},
+    vector::IVec2,
+};
+
+/// A [`Matrix`] surrounded by an endless background of one value, for puzzles where the area
+/// around the input matters too and may change each generation.
+///
+/// Positions are signed, with the input's top left cell at the origin. Writing outside the
+/// stored area grows it to fit.
+#[derive(Clone)]
+pub struct InfiniteGrid<T> {
+    matrix: Matrix<T>,
+    /// Where the matrix's top left cell is
+    origin: IVec2,
+    background: T,
+}
+
+impl<T> InfiniteGrid<T> {
+    pub fn new(matrix: Matrix<T>, background: T) -> Self {
+        InfiniteGrid {
+            matrix,
+            origin: IVec2::ZERO,
+            background,
+        }
+    }
+
+    /// The value at `point`, or the background if it is outside the stored area.
+    pub fn get(&self, point: IVec2) -> &T {
+        self.matrix
+            .get_signed(point - self.origin)
+            .unwrap_or(&self.background)
+    }
+
+    pub fn background(&self) -> &T {
+        &self.background
+    }
+
+    /// Changes every cell outside the stored area at once.
+    pub fn set_background(&mut self, background: T) {
+        self.background = background;
+    }
+
+    /// The stored area
+    pub fn matrix(&self) -> &Matrix<T> {
+        &self.matrix
+    }
+
+    /// Where the stored area's top left cell is
+    pub fn origin(&self) -> IVec2 {
+        self.origin
+    }
+}
+
+impl<T: Clone> InfiniteGrid<T> {
+    /// Adds `margin` cells of background on every side of the stored area.
+    pub fn grow(&mut self, margin: usize) {
+        let margin_vec = IVec2(margin as isize, margin as isize);
+        self.resize(self.origin - margin_vec, self.far_corner() + margin_vec);
+    }
+
+    /// Grows the stored area, if needed, so it includes `point`.
+    pub fn grow_to_include(&mut self, point: IVec2) {
+        let far = self.far_corner();
+        if self.matrix.get_signed(point - self.origin).is_none() {
+            let min = IVec2(self.origin.0.min(point.0), self.origin.1.min(point.1));
+            let max = IVec2(far.0.max(point.0), far.1.max(point.1));
+            self.resize(min, max);
+        }
+    }
+
+    /// The bottom right cell of the stored area
+    fn far_corner(&self) -> IVec2 {
+        let size = IVec2(
+            self.matrix.num_cols() as isize,
+            self.matrix.num_rows() as isize,
+        );
+        self.origin + size - IVec2(1, 1)
+    }
+
+    /// Replaces the stored area with the one between `min` and `max`, inclusive.
+    fn resize(&mut self, min: IVec2, max: IVec2) {
+        let size = max - min + IVec2(1, 1);
+        let matrix = Matrix::from_fn(size.1 as usize, size.0 as usize, |point| {
+            self.get(IVec2::from(point) + min).clone()
+        })
+        .expect("The stored area is always at least one cell wide");
+        self.matrix = matrix;
+        self.origin = min;
+    }
+
+    /// Builds the next generation, calling `rule` for each stored cell with its position, and
+    /// `background_rule` for the background. The stored area grows by `margin` first, so cells
+    /// next to it can change as well.
+    pub fn step(
+        &self,
+        margin: usize,
+        mut rule: impl FnMut(&Self, IVec2) -> T,
+        background_rule: impl FnOnce(&Self) -> T,
+    ) -> Self {
+        let mut next = self.clone();
+        next.grow(margin);
+        let origin = next.origin;
+        next.matrix = Matrix::from_fn(next.matrix.num_rows(), next.matrix.num_cols(), |point| {
+            rule(self, IVec2::from(point) + origin)
+        })
+        .expect("The stored area is always at least one cell wide");
+        next.background = background_rule(self);
+        next
+    }
+}
+
+impl<T> Grid<T> for InfiniteGrid<T> {
+    fn at(&self, point: IVec2) -> Option<&T> {
+        Some(&self[point])
+    }
+
+    fn bounds(&self) -> Option<(IVec2, IVec2)> {
+        let (min, max) = self.matrix.bounds()?;
+        Some((min + self.origin, max + self.origin))
+    }
+}
+
+impl<T> Index<IVec2> for InfiniteGrid<T> {
+    type Output = T;
+
+    fn index(&self, index: IVec2) -> &Self::Output {
+        self.get(index)
+    }
+}
+
+/// Writing to a cell grows the stored area to include it.
+impl<T: Clone> IndexMut<IVec2> for InfiniteGrid<T> {
+    fn index_mut(&mut self, index: IVec2) -> &mut Self::Output {
+        self.grow_to_include(index);
+        let point: Point = (index - self.origin).try_into().unwrap();
+        &mut self.matrix[point]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn background_and_growth() {
+        let matrix = Matrix::parse_with("#.\n.#", "# or .", |c| Some(c == '#')).unwrap();
+        let mut grid = InfiniteGrid::new(matrix, false);
+        assert!(grid[IVec2(1, 1)]);
+        assert!(!grid[IVec2(-5, 7)]);
+
+        grid[IVec2(-1, 3)] = true;
+        assert_eq!(grid.bounds(), Some((IVec2(-1, 0), IVec2(1, 3))));
+        assert!(grid[IVec2(0, 0)] && grid[IVec2(-1, 3)]);
+
+        // Flip everything, background included
+        let flipped = grid.step(1, |grid, point| !grid[point], |grid| !grid.background());
+        assert!(*flipped.background());
+        assert_eq!(flipped.bounds(), Some((IVec2(-2, -1), IVec2(2, 4))));
+        assert!(!flipped[IVec2(0, 0)] && flipped[IVec2(-2, -1)] && flipped[IVec2(50, 50)]);
+    }
+}
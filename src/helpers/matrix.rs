@@ -1,46 +1,137 @@
 use std::{
     fmt::Debug,
     ops::{Index, IndexMut},
+    str::FromStr,
 };
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+use super::{grid::Grid, vector::IVec2};
+use crate::parse_error::ParseError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Point(pub usize, pub usize);
 
+/// Which cells count as neighbours of a cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    /// Up, down, left and right
+    Four,
+    /// Diagonals as well
+    Eight,
+}
+
+impl Connectivity {
+    pub fn offsets(self) -> &'static [IVec2] {
+        match self {
+            Connectivity::Four => &IVec2::ORTHOGONAL,
+            Connectivity::Eight => &IVec2::SURROUNDING,
+        }
+    }
+}
+
 impl Point {
+    /// Points around this one that aren't negative. They may still be past the far edges of a
+    /// matrix; [`Matrix::neighbours`] only gives the ones inside it.
     pub fn surrounding_points(&self, diagonals: bool) -> impl Iterator<Item = Point> {
-        let mut points = Vec::with_capacity(8);
-        points.extend_from_slice(&[(-1, 0), (1, 0), (0, -1), (0, 1)]);
-        if diagonals {
-            points.extend_from_slice(&[(-1, -1), (1, -1), (1, 1), (-1, 1)]);
-        }
+        let connectivity = if diagonals {
+            Connectivity::Eight
+        } else {
+            Connectivity::Four
+        };
         let point = *self;
+        connectivity
+            .offsets()
+            .iter()
+            .filter_map(move |offset| point.offset(*offset))
+    }
 
-        points
-            .into_iter()
-            .map(move |rel| (point.0 as isize + rel.0, point.1 as isize + rel.1))
-            .filter(|out| out.0 >= 0 && out.1 >= 0)
-            .map(|out| Point(out.0 as usize, out.1 as usize))
+    /// Moves by `offset`, or returns `None` if that would make either coordinate negative.
+    pub fn offset(&self, offset: IVec2) -> Option<Point> {
+        (IVec2::from(*self) + offset).try_into().ok()
     }
 }
 
+#[derive(Clone, PartialEq, Eq, Hash)]
 pub struct Matrix<T> {
     cols: usize,
     data: Box<[T]>,
 }
 
 impl<T> Matrix<T> {
+    /// A matrix with no columns, such as the transpose of one with no rows, has no cells and so
+    /// no rows either.
     pub fn num_rows(&self) -> usize {
-        self.data.len() / self.cols
+        self.data.len().checked_div(self.cols).unwrap_or(0)
     }
 
     pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
-        self.data.chunks(self.num_cols())
+        // With no columns there's no data to split, but `chunks` still needs a non-zero size
+        self.data.chunks(self.cols.max(1))
     }
 
     pub fn num_cols(&self) -> usize {
         self.cols
     }
 
+    /// Every position in the matrix, row by row
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let cols = self.cols;
+        (0..self.num_rows()).flat_map(move |y| (0..cols).map(move |x| Point(x, y)))
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        self.rows().nth(y)
+    }
+
+    /// The values in column `x`, from top to bottom. Empty if `x` is out of range.
+    pub fn col(&self, x: usize) -> impl Iterator<Item = &T> + '_ {
+        let cells = if x < self.cols { &self.data[x..] } else { &[] };
+        cells.iter().step_by(self.cols)
+    }
+
+    pub fn cols(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.cols).map(move |x| self.col(x))
+    }
+
+    /// Borrows the `cols` by `rows` rectangle with its top left corner at `top_left`, or returns
+    /// `None` if it doesn't fit inside the matrix.
+    pub fn view(&self, top_left: Point, cols: usize, rows: usize) -> Option<MatrixView<'_, T>> {
+        if top_left.0 + cols > self.num_cols() || top_left.1 + rows > self.num_rows() {
+            return None;
+        }
+        Some(MatrixView {
+            matrix: self,
+            top_left,
+            cols,
+            rows,
+        })
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Matrix<U> {
+        Matrix {
+            cols: self.cols,
+            data: self.data.iter().map(f).collect(),
+        }
+    }
+
+    /// Builds a matrix by calling `f` with the position of each cell, row by row. Returns
+    /// `None` if there are no columns, as [`Matrix::from_cells`] does.
+    pub fn from_fn(rows: usize, cols: usize, f: impl FnMut(Point) -> T) -> Option<Self> {
+        if cols == 0 {
+            return None;
+        }
+        Some(Self::generate(rows, cols, f))
+    }
+
+    /// [`Matrix::from_fn`] without the check, for transforms of a matrix that already exists,
+    /// which can end up with no columns if it had no rows
+    fn generate(rows: usize, cols: usize, mut f: impl FnMut(Point) -> T) -> Self {
+        let data = (0..rows)
+            .flat_map(|y| (0..cols).map(move |x| Point(x, y)))
+            .map(&mut f)
+            .collect();
+        Matrix { cols, data }
+    }
+
     pub fn values(&self) -> impl Iterator<Item = &T> + '_ {
         self.data.iter()
     }
@@ -64,6 +155,70 @@ impl<T> Matrix<T> {
         let data_index = index.1 * self.cols + index.0;
         self.data.get_mut(data_index)
     }
+
+    /// The cells next to `point` that are inside the matrix, with their values.
+    pub fn neighbours(
+        &self,
+        point: Point,
+        connectivity: Connectivity,
+    ) -> impl Iterator<Item = (Point, &T)> + '_ {
+        point
+            .surrounding_points(connectivity == Connectivity::Eight)
+            .filter_map(move |neighbour| Some((neighbour, self.get(neighbour)?)))
+    }
+
+    /// Calls `update` on each of the cells next to `point` that are inside the matrix.
+    pub fn for_each_neighbour_mut(
+        &mut self,
+        point: Point,
+        connectivity: Connectivity,
+        mut update: impl FnMut(Point, &mut T),
+    ) {
+        for neighbour in point.surrounding_points(connectivity == Connectivity::Eight) {
+            if let Some(value) = self.get_mut(neighbour) {
+                update(neighbour, value);
+            }
+        }
+    }
+
+    /// Like [`get`](Self::get), for signed positions, which are out of range if negative.
+    pub fn get_signed(&self, index: IVec2) -> Option<&T> {
+        self.get(index.try_into().ok()?)
+    }
+
+    pub fn get_signed_mut(&mut self, index: IVec2) -> Option<&mut T> {
+        self.get_mut(index.try_into().ok()?)
+    }
+
+    /// Brings `index` into range by wrapping around the edges, as if the matrix were tiled
+    /// endlessly in every direction. Returns `None` if the matrix is empty, as nothing is in range.
+    pub fn wrap(&self, index: IVec2) -> Option<Point> {
+        Some(Point(
+            index.0.checked_rem_euclid(self.num_cols() as isize)? as usize,
+            index.1.checked_rem_euclid(self.num_rows() as isize)? as usize,
+        ))
+    }
+
+    pub fn get_wrapping(&self, index: IVec2) -> Option<&T> {
+        self.get(self.wrap(index)?)
+    }
+
+    pub fn get_wrapping_mut(&mut self, index: IVec2) -> Option<&mut T> {
+        self.get_mut(self.wrap(index)?)
+    }
+
+    /// The cells next to `point`, wrapping around the edges, with their values. Cells can come up
+    /// more than once in matrices less than 3 cells across, and there are none in an empty one.
+    pub fn neighbours_wrapping(
+        &self,
+        point: Point,
+        connectivity: Connectivity,
+    ) -> impl Iterator<Item = (Point, &T)> + '_ {
+        connectivity.offsets().iter().filter_map(move |offset| {
+            let neighbour = self.wrap(IVec2::from(point) + *offset)?;
+            Some((neighbour, &self[neighbour]))
+        })
+    }
 }
 
 impl<T: Copy> Matrix<T> {
@@ -78,6 +233,108 @@ impl<T: Copy> Matrix<T> {
     }
 }
 
+/// Transforms, which all return a new matrix
+impl<T: Clone> Matrix<T> {
+    /// Swaps rows and columns, so the first row becomes the first column.
+    pub fn transpose(&self) -> Self {
+        Self::generate(self.num_cols(), self.num_rows(), |Point(x, y)| {
+            self[Point(y, x)].clone()
+        })
+    }
+
+    /// Rotates a quarter turn clockwise, so the first row becomes the last column.
+    pub fn rotate_cw(&self) -> Self {
+        let rows = self.num_rows();
+        Self::generate(self.num_cols(), rows, |Point(x, y)| {
+            self[Point(y, rows - 1 - x)].clone()
+        })
+    }
+
+    /// Rotates a quarter turn anticlockwise, so the first row becomes the first column, upside
+    /// down.
+    pub fn rotate_ccw(&self) -> Self {
+        let cols = self.num_cols();
+        Self::generate(cols, self.num_rows(), |Point(x, y)| {
+            self[Point(cols - 1 - y, x)].clone()
+        })
+    }
+
+    /// Mirrors left to right.
+    pub fn flip_horizontal(&self) -> Self {
+        let cols = self.num_cols();
+        Self::generate(self.num_rows(), cols, |Point(x, y)| {
+            self[Point(cols - 1 - x, y)].clone()
+        })
+    }
+
+    /// Mirrors top to bottom.
+    pub fn flip_vertical(&self) -> Self {
+        let rows = self.num_rows();
+        Self::generate(rows, self.num_cols(), |Point(x, y)| {
+            self[Point(x, rows - 1 - y)].clone()
+        })
+    }
+}
+
+/// A rectangle borrowed from a [`Matrix`], indexed from its own top left corner
+#[derive(Clone, Copy)]
+pub struct MatrixView<'m, T> {
+    matrix: &'m Matrix<T>,
+    top_left: Point,
+    cols: usize,
+    rows: usize,
+}
+
+impl<'m, T> MatrixView<'m, T> {
+    pub fn num_rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn num_cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn get(&self, point: Point) -> Option<&'m T> {
+        if point.0 >= self.cols || point.1 >= self.rows {
+            return None;
+        }
+        self.matrix
+            .get(Point(self.top_left.0 + point.0, self.top_left.1 + point.1))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &'m [T]> + 'm {
+        let Point(left, top) = self.top_left;
+        let cols = self.cols;
+        self.matrix
+            .rows()
+            .skip(top)
+            .take(self.rows)
+            .map(move |row| &row[left..left + cols])
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &'m T> + 'm {
+        self.rows().flatten()
+    }
+}
+
+impl<'m, T: Clone> MatrixView<'m, T> {
+    /// Copies the view into a matrix of its own.
+    pub fn to_matrix(&self) -> Matrix<T> {
+        Matrix {
+            cols: self.cols,
+            data: self.values().cloned().collect(),
+        }
+    }
+}
+
+impl<'m, T> Index<Point> for MatrixView<'m, T> {
+    type Output = T;
+
+    fn index(&self, index: Point) -> &Self::Output {
+        self.get(index).unwrap()
+    }
+}
+
 impl<T> Index<Point> for Matrix<T> {
     type Output = T;
 
@@ -92,6 +349,34 @@ impl<T> IndexMut<Point> for Matrix<T> {
     }
 }
 
+impl<T> Index<IVec2> for Matrix<T> {
+    type Output = T;
+
+    fn index(&self, index: IVec2) -> &Self::Output {
+        self.get_signed(index).unwrap()
+    }
+}
+
+impl<T> IndexMut<IVec2> for Matrix<T> {
+    fn index_mut(&mut self, index: IVec2) -> &mut Self::Output {
+        self.get_signed_mut(index).unwrap()
+    }
+}
+
+impl<T> Grid<T> for Matrix<T> {
+    fn at(&self, point: IVec2) -> Option<&T> {
+        self.get_signed(point)
+    }
+
+    fn bounds(&self) -> Option<(IVec2, IVec2)> {
+        let bottom_right = Point(
+            self.num_cols().checked_sub(1)?,
+            self.num_rows().checked_sub(1)?,
+        );
+        Some((IVec2::ZERO, bottom_right.into()))
+    }
+}
+
 impl Debug for Matrix<u8> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         for row in self.rows() {
@@ -104,17 +389,208 @@ impl Debug for Matrix<u8> {
     }
 }
 
-impl Matrix<u8> {
-    pub fn parse_from_table(table: &str) -> anyhow::Result<Self> {
-        let lines: Vec<&str> = table.lines().collect();
-        let rows = lines.len();
-        let cols = lines[0].len();
-        let mut out = Matrix::new(rows, cols, 0u8);
-        for (row_idx, line) in lines.iter().enumerate() {
-            for (col_idx, char_) in line.chars().enumerate() {
-                out[Point(col_idx, row_idx)] = String::from(char_).parse()?;
+impl<T> Matrix<T> {
+    /// Builds a matrix from cells listed row by row. Returns `None` if the cells don't fill a
+    /// whole number of rows.
+    pub fn from_cells(cols: usize, cells: Vec<T>) -> Option<Self> {
+        if cols == 0 || !cells.len().is_multiple_of(cols) {
+            return None;
+        }
+        Some(Matrix {
+            cols,
+            data: cells.into_boxed_slice(),
+        })
+    }
+
+    /// Parses a grid with one character per cell, such as `#.#` maps or letter grids.
+    ///
+    /// `cell` maps each character to a value, returning `None` for characters that aren't
+    /// allowed, which are reported as not being `expected`.
+    pub fn parse_with(
+        table: &str,
+        expected: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        Self::parse_rows(table, |line_idx, line| {
+            line.chars()
+                .enumerate()
+                .map(|(col_idx, char_)| {
+                    cell(char_).ok_or_else(|| {
+                        ParseError::new(line, expected)
+                            .on_line(line_idx)
+                            .at_column(col_idx)
+                    })
+                })
+                .collect()
+        })
+    }
+
+    /// Parses each line of `table` into a row with `row`, checking that all rows are as long as
+    /// the first.
+    fn parse_rows(
+        table: &str,
+        mut row: impl FnMut(usize, &str) -> Result<Vec<T>, ParseError>,
+    ) -> Result<Self, ParseError> {
+        let mut cols = None;
+        let mut cells = Vec::new();
+        for (line_idx, line) in table.lines().enumerate() {
+            let parsed = row(line_idx, line)?;
+            let expected_cols = *cols.get_or_insert(parsed.len());
+            if parsed.len() != expected_cols || expected_cols == 0 {
+                let expected = format!("a row of {} cells, like the first", expected_cols);
+                return Err(ParseError::new(line, expected).on_line(line_idx));
             }
+            cells.extend(parsed);
         }
-        Ok(out)
+        let cols = cols.ok_or_else(|| ParseError::new("", "a grid"))?;
+        Ok(Matrix {
+            cols,
+            data: cells.into_boxed_slice(),
+        })
+    }
+}
+
+impl<T: FromStr> Matrix<T> {
+    /// Parses a grid with cells separated by `delimiter`, or by runs of whitespace if there is
+    /// none, such as bingo boards. Cells that don't parse are reported as not being `expected`.
+    pub fn parse_delimited(
+        table: &str,
+        delimiter: Option<char>,
+        expected: &str,
+    ) -> Result<Self, ParseError> {
+        Self::parse_rows(table, |line_idx, line| {
+            let cells: Box<dyn Iterator<Item = &str>> = match delimiter {
+                Some(delimiter) => Box::new(line.split(delimiter)),
+                None => Box::new(line.split_whitespace()),
+            };
+            cells
+                .map(|cell| {
+                    cell.trim()
+                        .parse()
+                        .map_err(|_| ParseError::at(line, cell, expected).on_line(line_idx))
+                })
+                .collect()
+        })
+    }
+}
+
+impl Matrix<u8> {
+    /// Parses a grid of single digits.
+    pub fn parse_from_table(table: &str) -> Result<Self, ParseError> {
+        Self::parse_with(table, "a digit", |char_| {
+            char_.to_digit(10).map(|digit| digit as u8)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn neighbours_stay_in_bounds() {
+        let matrix = Matrix::parse_from_table("123\n456").unwrap();
+        let corner: Vec<_> = matrix.neighbours(Point(2, 0), Connectivity::Four).collect();
+        assert_eq!(corner, [(Point(2, 1), &6), (Point(1, 0), &2)]);
+        assert_eq!(
+            matrix.neighbours(Point(1, 1), Connectivity::Eight).count(),
+            5
+        );
+
+        let mut matrix = matrix;
+        matrix.for_each_neighbour_mut(Point(0, 0), Connectivity::Eight, |_, value| *value = 0);
+        assert_eq!(matrix.values().filter(|value| **value == 0).count(), 3);
+    }
+
+    #[test]
+    fn transforms() {
+        let matrix = Matrix::parse_from_table("123\n456").unwrap();
+        let rows = |matrix: Matrix<u8>| matrix.rows().map(<[u8]>::to_vec).collect::<Vec<_>>();
+        assert_eq!(rows(matrix.transpose()), [[1, 4], [2, 5], [3, 6]]);
+        assert_eq!(rows(matrix.rotate_cw()), [[4, 1], [5, 2], [6, 3]]);
+        assert_eq!(rows(matrix.rotate_ccw()), [[3, 6], [2, 5], [1, 4]]);
+        assert_eq!(rows(matrix.flip_horizontal()), [[3, 2, 1], [6, 5, 4]]);
+        assert_eq!(rows(matrix.flip_vertical()), [[4, 5, 6], [1, 2, 3]]);
+        assert_eq!(matrix.col(1).collect::<Vec<_>>(), [&2, &5]);
+        assert_eq!(matrix.col(3).count(), 0);
+        assert_eq!(matrix.rotate_cw().rotate_ccw().row(1), Some(&[4, 5, 6][..]));
+
+        let empty = Matrix::<u8>::from_cells(3, vec![]).unwrap();
+        for transformed in [empty.transpose(), empty.rotate_cw(), empty.rotate_ccw()] {
+            assert_eq!((transformed.num_cols(), transformed.num_rows()), (0, 0));
+            assert_eq!(transformed.rows().count(), 0);
+            assert_eq!(transformed.points().count(), 0);
+        }
+    }
+
+    #[test]
+    fn wrapping() {
+        let matrix = Matrix::parse_from_table("123\n456").unwrap();
+        assert_eq!(matrix.wrap(IVec2(-1, 2)), Some(Point(2, 0)));
+        assert_eq!(matrix.get_wrapping(IVec2(4, -1)), Some(&5));
+        let neighbours: Vec<_> = matrix
+            .neighbours_wrapping(Point(0, 0), Connectivity::Four)
+            .map(|(_, value)| *value)
+            .collect();
+        assert_eq!(neighbours, [4, 2, 4, 3]);
+
+        let empty = Matrix::<u8>::from_cells(3, vec![]).unwrap();
+        assert_eq!(empty.wrap(IVec2(1, 1)), None);
+        assert_eq!(empty.get_wrapping(IVec2(1, 1)), None);
+        assert_eq!(
+            empty
+                .neighbours_wrapping(Point(0, 0), Connectivity::Eight)
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn views() {
+        let matrix = Matrix::parse_from_table("1234\n5678\n9012").unwrap();
+        let view = matrix.view(Point(1, 1), 2, 2).unwrap();
+        assert_eq!(view[Point(0, 0)], 6);
+        assert_eq!(view.get(Point(2, 0)), None);
+        assert_eq!(view.values().copied().collect::<Vec<_>>(), [6, 7, 0, 1]);
+        assert_eq!(view.to_matrix().row(1), Some(&[0, 1][..]));
+        assert!(matrix.view(Point(3, 0), 2, 1).is_none());
+    }
+
+    #[test]
+    fn parse_with_mapping() {
+        let matrix = Matrix::parse_with("#.\n.#\n..", "# or .", |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+        .unwrap();
+        assert_eq!((matrix.num_cols(), matrix.num_rows()), (2, 3));
+        assert!(matrix[Point(1, 1)]);
+        assert!(!matrix[Point(0, 1)]);
+
+        let err = Matrix::parse_with("#.\n.x", "# or .", |c| matches!(c, '#' | '.').then_some(c))
+            .err()
+            .unwrap();
+        assert_eq!((err.line, err.column), (2, 2));
+    }
+
+    #[test]
+    fn ragged_and_empty_tables() {
+        let err = Matrix::parse_from_table("123\n45\n678").unwrap_err();
+        assert_eq!(err.line, 2);
+        assert!(Matrix::parse_from_table("").is_err());
+        assert_eq!(Matrix::from_fn(3, 0, |_| 0), None);
+    }
+
+    #[test]
+    fn parse_delimited_numbers() {
+        let matrix: Matrix<u8> =
+            Matrix::parse_delimited("22 13  7\n 8  2 23", None, "a number").unwrap();
+        assert_eq!(matrix.rows().nth(1), Some(&[8, 2, 23][..]));
+        let matrix: Matrix<i32> =
+            Matrix::parse_delimited("1,-2\n3,4", Some(','), "a number").unwrap();
+        assert_eq!(matrix[Point(1, 0)], -2);
+        let err = Matrix::<u8>::parse_delimited("1 2\n3 x", None, "a number").unwrap_err();
+        assert_eq!((err.line, err.column, err.found()), (2, 3, "x".to_owned()));
     }
 }
@@ -1 +1,11 @@
+pub mod automaton;
+pub mod components;
+pub mod disjoint_set;
+pub mod grid;
+pub mod grid_nd;
+pub mod infinite_grid;
 pub mod matrix;
+pub mod ocr;
+pub mod search;
+pub mod sparse_grid;
+pub mod vector;
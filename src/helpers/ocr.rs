@@ -0,0 +1,66 @@
+use super::matrix::{Matrix, Point};
+
+/// The capital letters puzzles draw in dots, each 4 cells wide and 6 tall
+const FONT: [(char, [&str; LETTER_HEIGHT]); 16] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+pub const LETTER_WIDTH: usize = 4;
+pub const LETTER_HEIGHT: usize = 6;
+
+/// Letters are separated by a blank column
+const LETTER_SPACING: usize = LETTER_WIDTH + 1;
+
+/// Reads the letters drawn in `dots`, which must be exactly one letter tall. Returns `None` if
+/// any of them isn't in the font.
+pub fn recognise(dots: &Matrix<bool>) -> Option<String> {
+    if dots.num_rows() != LETTER_HEIGHT || dots.num_cols() < LETTER_WIDTH {
+        return None;
+    }
+    let num_letters = dots.num_cols().div_ceil(LETTER_SPACING);
+    (0..num_letters)
+        .map(|letter_idx| {
+            let left = letter_idx * LETTER_SPACING;
+            FONT.iter()
+                .find(|(_, glyph)| {
+                    glyph.iter().enumerate().all(|(y, row)| {
+                        row.chars().enumerate().all(|(x, cell)| {
+                            dots.get(Point(left + x, y)).copied().unwrap_or(false) == (cell == '#')
+                        })
+                    })
+                })
+                .map(|(letter, _)| *letter)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn recognises_letters() {
+        let text = "#..#.###..####.\n#..#.#..#.#....\n####.###..###..\n\
+                    #..#.#..#.#....\n#..#.#..#.#....\n#..#.###..####.";
+        let dots = Matrix::parse_with(text, "# or .", |c| Some(c == '#')).unwrap();
+        assert_eq!(recognise(&dots).as_deref(), Some("HBE"));
+
+        let blot = Matrix::parse_with(&text.replace('.', "#"), "#", |c| Some(c == '#')).unwrap();
+        assert_eq!(recognise(&blot), None);
+    }
+}
@@ -0,0 +1,262 @@
+use std::{
+    cmp::Ordering,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+};
+
+use super::{
+    matrix::{Connectivity, Matrix, Point},
+    vector::IVec2,
+};
+
+pub type Cost = u64;
+
+/// Everything a search reached from its start, with the cost of getting there and the way back.
+#[derive(Debug, Clone)]
+pub struct SearchResult<N> {
+    start: N,
+    distances: HashMap<N, Cost>,
+    predecessors: HashMap<N, N>,
+}
+
+impl<N: Copy + Eq + Hash> SearchResult<N> {
+    fn new(start: N) -> Self {
+        SearchResult {
+            start,
+            distances: HashMap::from([(start, 0)]),
+            predecessors: HashMap::new(),
+        }
+    }
+
+    /// The cost of the cheapest path to `node`, or the number of steps for [`bfs`]. `None` if it
+    /// wasn't reached. After [`astar`], only the goal's is sure to be the cheapest; see there.
+    pub fn distance(&self, node: N) -> Option<Cost> {
+        self.distances.get(&node).copied()
+    }
+
+    /// The node before `node` on the path to it. `None` for the start and nodes not reached.
+    pub fn predecessor(&self, node: N) -> Option<N> {
+        self.predecessors.get(&node).copied()
+    }
+
+    /// The nodes from the start to `node`, inclusive, or `None` if it wasn't reached.
+    pub fn path_to(&self, node: N) -> Option<Vec<N>> {
+        self.distances.get(&node)?;
+        let mut path = vec![node];
+        let mut current = node;
+        while current != self.start {
+            current = self.predecessors[&current];
+            path.push(current);
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// The nodes reached, in no particular order
+    pub fn reached(&self) -> impl Iterator<Item = N> + '_ {
+        self.distances.keys().copied()
+    }
+
+    pub fn num_reached(&self) -> usize {
+        self.distances.len()
+    }
+}
+
+/// Breadth-first search of every node reachable from `start`, where each step costs 1.
+pub fn bfs<N, I>(start: N, mut neighbours: impl FnMut(N) -> I) -> SearchResult<N>
+where
+    N: Copy + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut result = SearchResult::new(start);
+    let mut queue = VecDeque::from([start]);
+    while let Some(node) = queue.pop_front() {
+        let distance = result.distances[&node] + 1;
+        for next in neighbours(node) {
+            if let Entry::Vacant(entry) = result.distances.entry(next) {
+                entry.insert(distance);
+                result.predecessors.insert(next, node);
+                queue.push_back(next);
+            }
+        }
+    }
+    result
+}
+
+/// Cheapest paths to every node reachable from `start`. `neighbours` gives the nodes next to a
+/// node with the cost of moving to each.
+pub fn dijkstra<N, I>(start: N, neighbours: impl FnMut(N) -> I) -> SearchResult<N>
+where
+    N: Copy + Eq + Hash,
+    I: IntoIterator<Item = (N, Cost)>,
+{
+    best_first(start, None, neighbours, |_| 0)
+}
+
+/// Cheapest path from `start` to `goal`, stopping once it is found. `heuristic` estimates the
+/// cost from a node to the goal, and must never overestimate it for the path to be the cheapest.
+///
+/// As the search stops early, the distances and paths to nodes other than the goal may only be
+/// the best found so far, not the cheapest.
+pub fn astar<N, I>(
+    start: N,
+    goal: N,
+    neighbours: impl FnMut(N) -> I,
+    heuristic: impl FnMut(N) -> Cost,
+) -> SearchResult<N>
+where
+    N: Copy + Eq + Hash,
+    I: IntoIterator<Item = (N, Cost)>,
+{
+    best_first(start, Some(goal), neighbours, heuristic)
+}
+
+fn best_first<N, I>(
+    start: N,
+    goal: Option<N>,
+    mut neighbours: impl FnMut(N) -> I,
+    mut heuristic: impl FnMut(N) -> Cost,
+) -> SearchResult<N>
+where
+    N: Copy + Eq + Hash,
+    I: IntoIterator<Item = (N, Cost)>,
+{
+    let mut result = SearchResult::new(start);
+    let mut queue = BinaryHeap::from([Queued {
+        priority: heuristic(start),
+        cost: 0,
+        node: start,
+    }]);
+    while let Some(Queued { cost, node, .. }) = queue.pop() {
+        if Some(node) == goal {
+            break;
+        }
+        // Skip entries left behind after a cheaper path was found
+        if cost > result.distances[&node] {
+            continue;
+        }
+        for (next, step_cost) in neighbours(node) {
+            let next_cost = cost + step_cost;
+            if result
+                .distance(next)
+                .is_some_and(|known| known <= next_cost)
+            {
+                continue;
+            }
+            result.distances.insert(next, next_cost);
+            result.predecessors.insert(next, node);
+            queue.push(Queued {
+                priority: next_cost + heuristic(next),
+                cost: next_cost,
+                node: next,
+            });
+        }
+    }
+    result
+}
+
+/// A node waiting to be visited, ordered so the lowest priority comes out of the heap first
+struct Queued<N> {
+    priority: Cost,
+    cost: Cost,
+    node: N,
+}
+
+impl<N> PartialEq for Queued<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<N> Eq for Queued<N> {}
+
+impl<N> PartialOrd for Queued<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N> Ord for Queued<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+/// Cheapest paths from `start` to every cell of `matrix`, where entering a cell costs its value.
+pub fn matrix_dijkstra<T: Copy + Into<Cost>>(
+    matrix: &Matrix<T>,
+    start: Point,
+    connectivity: Connectivity,
+) -> SearchResult<Point> {
+    dijkstra(start, |point| cell_costs(matrix, point, connectivity))
+}
+
+/// Cheapest path from `start` to `goal` across `matrix`, where entering a cell costs its value.
+/// Only the goal's distance and path are sure to be the cheapest, as for [`astar`].
+pub fn matrix_astar<T: Copy + Into<Cost>>(
+    matrix: &Matrix<T>,
+    start: Point,
+    goal: Point,
+    connectivity: Connectivity,
+) -> SearchResult<Point> {
+    // Every remaining step costs at least the cheapest cell
+    let min_cost = matrix
+        .values()
+        .map(|value| (*value).into())
+        .min()
+        .unwrap_or(0);
+    let heuristic = |point: Point| {
+        let point = IVec2::from(point);
+        let steps = match connectivity {
+            Connectivity::Four => point.manhattan_distance(goal.into()),
+            Connectivity::Eight => point.chebyshev_distance(goal.into()),
+        };
+        steps as Cost * min_cost
+    };
+    let neighbours = |point| cell_costs(matrix, point, connectivity);
+    astar(start, goal, neighbours, heuristic)
+}
+
+/// The cells next to `point`, each with its value as the cost of moving there
+fn cell_costs<T: Copy + Into<Cost>>(
+    matrix: &Matrix<T>,
+    point: Point,
+    connectivity: Connectivity,
+) -> impl Iterator<Item = (Point, Cost)> + '_ {
+    matrix
+        .neighbours(point, connectivity)
+        .map(|(neighbour, value)| (neighbour, (*value).into()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn bfs_over_a_graph() {
+        let edges = [(1, 2), (1, 3), (2, 4), (3, 4), (4, 5), (6, 7)];
+        let result = bfs(1, |node| {
+            edges
+                .iter()
+                .filter(move |(from, _)| *from == node)
+                .map(|(_, to)| *to)
+        });
+        assert_eq!(result.distance(5), Some(3));
+        assert_eq!(result.path_to(5), Some(vec![1, 2, 4, 5]));
+        assert_eq!(result.path_to(6), None);
+        assert_eq!(result.num_reached(), 5);
+    }
+
+    #[test]
+    fn cheapest_paths_across_a_matrix() {
+        let matrix = Matrix::parse_from_table("1163\n1381\n2136\n3694").unwrap();
+        let goal = Point(3, 3);
+        let all = matrix_dijkstra(&matrix, Point(0, 0), Connectivity::Four);
+        let to_goal = matrix_astar(&matrix, Point(0, 0), goal, Connectivity::Four);
+        assert_eq!(all.distance(goal), Some(17));
+        assert_eq!(to_goal.distance(goal), Some(17));
+        let path = to_goal.path_to(goal).unwrap();
+        let cost: Cost = path[1..].iter().map(|point| matrix[*point] as Cost).sum();
+        assert_eq!(cost, 17);
+    }
+}
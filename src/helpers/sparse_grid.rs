@@ -0,0 +1,124 @@
+use std::{
+    collections::HashMap,
+    ops::{Index, IndexMut},
+};
+
+use super::{grid::Grid, vector::IVec2};
+
+/// A grid that only stores the cells that have been written to, for coordinates that are huge,
+/// negative or unbounded. Every other cell reads as the default value.
+#[derive(Debug, Clone)]
+pub struct SparseGrid<T> {
+    default: T,
+    cells: HashMap<IVec2, T>,
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new(default: T) -> Self {
+        SparseGrid {
+            default,
+            cells: HashMap::new(),
+        }
+    }
+
+    /// The number of cells that have been written to
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The value written to `point`, or `None` if it still has the default value.
+    pub fn get(&self, point: IVec2) -> Option<&T> {
+        self.cells.get(&point)
+    }
+
+    pub fn get_mut(&mut self, point: IVec2) -> Option<&mut T> {
+        self.cells.get_mut(&point)
+    }
+
+    pub fn insert(&mut self, point: IVec2, value: T) -> Option<T> {
+        self.cells.insert(point, value)
+    }
+
+    pub fn remove(&mut self, point: IVec2) -> Option<T> {
+        self.cells.remove(&point)
+    }
+
+    /// The values that have been written, in no particular order
+    pub fn values(&self) -> impl Iterator<Item = &T> + '_ {
+        self.cells.values()
+    }
+
+    pub fn value_mut(&mut self) -> impl Iterator<Item = &mut T> + '_ {
+        self.cells.values_mut()
+    }
+}
+
+impl<T: Copy> SparseGrid<T> {
+    /// The cells that have been written, in no particular order
+    pub fn enumerate(&self) -> impl Iterator<Item = (IVec2, T)> + '_ {
+        self.cells.iter().map(|(point, value)| (*point, *value))
+    }
+}
+
+impl<T> Grid<T> for SparseGrid<T> {
+    fn at(&self, point: IVec2) -> Option<&T> {
+        Some(&self[point])
+    }
+
+    fn bounds(&self) -> Option<(IVec2, IVec2)> {
+        let mut points = self.cells.keys();
+        let first = *points.next()?;
+        Some(points.fold((first, first), |(min, max), point| {
+            (
+                IVec2(min.0.min(point.0), min.1.min(point.1)),
+                IVec2(max.0.max(point.0), max.1.max(point.1)),
+            )
+        }))
+    }
+}
+
+impl<T> Index<IVec2> for SparseGrid<T> {
+    type Output = T;
+
+    fn index(&self, index: IVec2) -> &Self::Output {
+        self.cells.get(&index).unwrap_or(&self.default)
+    }
+}
+
+/// Writing to a cell stores it, starting from the default value.
+impl<T: Clone> IndexMut<IVec2> for SparseGrid<T> {
+    fn index_mut(&mut self, index: IVec2) -> &mut Self::Output {
+        self.cells
+            .entry(index)
+            .or_insert_with(|| self.default.clone())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn sparse_cells_and_rendering() {
+        let mut grid = SparseGrid::new(0u32);
+        grid[IVec2(-1_000_000, 3)] += 2;
+        grid[IVec2(-999_998, 2)] += 1;
+        grid[IVec2(-999_998, 2)] += 1;
+        assert_eq!(grid.len(), 2);
+        assert_eq!(grid[IVec2(5, 5)], 0);
+        assert_eq!(grid.get(IVec2(5, 5)), None);
+        assert_eq!(
+            grid.bounds(),
+            Some((IVec2(-1_000_000, 2), IVec2(-999_998, 3)))
+        );
+        let rendered = grid.render(|value| match value {
+            Some(0) | None => '.',
+            Some(count) => char::from_digit(*count, 10).unwrap(),
+        });
+        assert_eq!(rendered, "..2\n2..\n");
+    }
+}
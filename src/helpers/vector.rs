@@ -0,0 +1,169 @@
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use super::matrix::Point;
+
+/// A signed 2D vector `(x, y)`, for positions that can go negative, velocities and offsets.
+///
+/// Like [`Point`], `y` grows downwards, so [`UP`](Self::UP) is `(0, -1)` and rotating
+/// clockwise turns up into right.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct IVec2(pub isize, pub isize);
+
+impl IVec2 {
+    pub const ZERO: IVec2 = IVec2(0, 0);
+    pub const UP: IVec2 = IVec2(0, -1);
+    pub const DOWN: IVec2 = IVec2(0, 1);
+    pub const LEFT: IVec2 = IVec2(-1, 0);
+    pub const RIGHT: IVec2 = IVec2(1, 0);
+
+    /// Up, right, down and left, in clockwise order
+    pub const ORTHOGONAL: [IVec2; 4] = [Self::UP, Self::RIGHT, Self::DOWN, Self::LEFT];
+
+    /// Up-right, down-right, down-left and up-left, in clockwise order
+    pub const DIAGONAL: [IVec2; 4] = [IVec2(1, -1), IVec2(1, 1), IVec2(-1, 1), IVec2(-1, -1)];
+
+    /// The orthogonal directions followed by the diagonal ones
+    pub const SURROUNDING: [IVec2; 8] = [
+        Self::UP,
+        Self::RIGHT,
+        Self::DOWN,
+        Self::LEFT,
+        IVec2(1, -1),
+        IVec2(1, 1),
+        IVec2(-1, 1),
+        IVec2(-1, -1),
+    ];
+
+    /// Distance from the origin moving only orthogonally
+    pub fn manhattan(self) -> usize {
+        self.0.unsigned_abs() + self.1.unsigned_abs()
+    }
+
+    /// Distance from the origin moving diagonally as well
+    pub fn chebyshev(self) -> usize {
+        self.0.unsigned_abs().max(self.1.unsigned_abs())
+    }
+
+    pub fn manhattan_distance(self, other: IVec2) -> usize {
+        (other - self).manhattan()
+    }
+
+    pub fn chebyshev_distance(self, other: IVec2) -> usize {
+        (other - self).chebyshev()
+    }
+
+    /// Each component reduced to -1, 0 or 1, giving the direction of a horizontal, vertical or
+    /// 45° vector as a single step
+    pub fn signum(self) -> IVec2 {
+        IVec2(self.0.signum(), self.1.signum())
+    }
+
+    /// Rotates a quarter turn clockwise about the origin
+    pub fn rotate_cw(self) -> IVec2 {
+        IVec2(-self.1, self.0)
+    }
+
+    /// Rotates a quarter turn anticlockwise about the origin
+    pub fn rotate_ccw(self) -> IVec2 {
+        IVec2(self.1, -self.0)
+    }
+
+    pub fn rotate_180(self) -> IVec2 {
+        -self
+    }
+}
+
+impl Add for IVec2 {
+    type Output = IVec2;
+
+    fn add(self, rhs: IVec2) -> IVec2 {
+        IVec2(self.0 + rhs.0, self.1 + rhs.1)
+    }
+}
+
+impl Sub for IVec2 {
+    type Output = IVec2;
+
+    fn sub(self, rhs: IVec2) -> IVec2 {
+        IVec2(self.0 - rhs.0, self.1 - rhs.1)
+    }
+}
+
+impl Neg for IVec2 {
+    type Output = IVec2;
+
+    fn neg(self) -> IVec2 {
+        IVec2(-self.0, -self.1)
+    }
+}
+
+impl Mul<isize> for IVec2 {
+    type Output = IVec2;
+
+    fn mul(self, rhs: isize) -> IVec2 {
+        IVec2(self.0 * rhs, self.1 * rhs)
+    }
+}
+
+impl AddAssign for IVec2 {
+    fn add_assign(&mut self, rhs: IVec2) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for IVec2 {
+    fn sub_assign(&mut self, rhs: IVec2) {
+        *self = *self - rhs;
+    }
+}
+
+impl MulAssign<isize> for IVec2 {
+    fn mul_assign(&mut self, rhs: isize) {
+        *self = *self * rhs;
+    }
+}
+
+impl From<Point> for IVec2 {
+    fn from(point: Point) -> Self {
+        IVec2(point.0 as isize, point.1 as isize)
+    }
+}
+
+impl TryFrom<IVec2> for Point {
+    type Error = IVec2;
+
+    /// Fails, returning the vector, if either component is negative.
+    fn try_from(vector: IVec2) -> Result<Self, Self::Error> {
+        match (usize::try_from(vector.0), usize::try_from(vector.1)) {
+            (Ok(x), Ok(y)) => Ok(Point(x, y)),
+            _ => Err(vector),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rotation_and_distance() {
+        assert_eq!(IVec2::UP.rotate_cw(), IVec2::RIGHT);
+        assert_eq!(IVec2::RIGHT.rotate_ccw(), IVec2::UP);
+        let mut direction = IVec2(2, -1);
+        for _ in 0..4 {
+            direction = direction.rotate_cw();
+        }
+        assert_eq!(direction, IVec2(2, -1));
+        assert_eq!(IVec2(3, -4).manhattan(), 7);
+        assert_eq!(IVec2(3, -4).chebyshev(), 4);
+        assert_eq!(IVec2(1, 1).manhattan_distance(IVec2(-2, 3)), 5);
+    }
+
+    #[test]
+    fn point_conversion() {
+        assert_eq!(Point::try_from(IVec2(2, 3)), Ok(Point(2, 3)));
+        assert_eq!(Point::try_from(IVec2(2, -3)), Err(IVec2(2, -3)));
+        assert_eq!(Point(4, 1).offset(IVec2::UP * 2), None);
+        assert_eq!(Point(4, 1).offset(IVec2::LEFT), Some(Point(3, 1)));
+    }
+}
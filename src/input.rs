@@ -0,0 +1,65 @@
+use anyhow::anyhow;
+use std::{
+    fmt::Display,
+    fs::File,
+    io::{stdin, BufRead, BufReader, Read},
+    path::PathBuf,
+};
+
+/// Where puzzle inputs are looked for when no path is given.
+pub const DEFAULT_INPUTS_DIR: &str = "inputs";
+
+/// Where to read a day's puzzle input from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Stdin,
+    File(PathBuf),
+}
+
+impl InputSource {
+    /// `inputs/dayNN.txt`
+    pub fn default_for(day: u8) -> Self {
+        InputSource::File(PathBuf::from(DEFAULT_INPUTS_DIR).join(format!("day{:02}.txt", day)))
+    }
+
+    /// Interprets an `--input` argument, where `-` means stdin. Without one, uses the default
+    /// path for `day`.
+    pub fn from_arg(arg: Option<&str>, day: u8) -> Self {
+        match arg {
+            Some("-") => InputSource::Stdin,
+            Some(path) => InputSource::File(PathBuf::from(path)),
+            None => Self::default_for(day),
+        }
+    }
+
+    pub fn open(&self) -> anyhow::Result<Box<dyn BufRead>> {
+        match self {
+            InputSource::Stdin => Ok(Box::new(stdin().lock())),
+            InputSource::File(path) => {
+                let file = File::open(path).map_err(|err| {
+                    anyhow!(
+                        "Could not open {}: {} (pass --input <path>, or --input - for stdin)",
+                        path.display(),
+                        err
+                    )
+                })?;
+                Ok(Box::new(BufReader::new(file)))
+            }
+        }
+    }
+
+    pub fn read_to_string(&self) -> anyhow::Result<String> {
+        let mut out = String::new();
+        self.open()?.read_to_string(&mut out)?;
+        Ok(out)
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputSource::Stdin => write!(f, "stdin"),
+            InputSource::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
@@ -1,13 +1,39 @@
+pub mod answer;
+pub mod bench;
 pub mod helpers;
+pub mod input;
+pub mod logging;
+pub mod parse_error;
+pub mod runner;
+pub mod scaffold;
+pub mod solution;
 
-pub mod day01;
-pub mod day02;
-pub mod day03;
-pub mod day04;
-pub mod day05;
-pub mod day06;
-pub mod day07;
-pub mod day08;
-pub mod day09;
-pub mod day10;
-pub mod day11;
+use solution::Solution;
+
+/// Declares each day module and adds its `Day` to [`SOLUTIONS`]. New days only need adding here.
+macro_rules! days {
+    ($($module:ident),* $(,)?) => {
+        $(pub mod $module;)*
+
+        static SOLUTIONS: &[&dyn Solution] = &[$(&$module::Day),*];
+    };
+}
+
+days! {
+    day01,
+    day02,
+    day03,
+    day04,
+    day05,
+    day06,
+    day07,
+    day08,
+    day09,
+    day10,
+    day11,
+    day12,
+    day13,
+    day14,
+    day15,
+    day16,
+}
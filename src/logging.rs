@@ -0,0 +1,110 @@
+use log::{LevelFilter, Log, Metadata, Record, SetLoggerError};
+
+/// Writes log records to stderr.
+///
+/// Everything is logged at `level`, except for targets named with [`trace`](Self::trace), which
+/// are logged in full. Days log under their module path, so `day11` matches everything logged
+/// by the day 11 solution.
+pub struct Logger {
+    level: LevelFilter,
+    traced: Vec<String>,
+}
+
+impl Logger {
+    pub fn new(level: LevelFilter) -> Self {
+        Logger {
+            level,
+            traced: Vec::new(),
+        }
+    }
+
+    /// Logs everything under `target`, whatever the overall level.
+    pub fn trace(mut self, target: impl Into<String>) -> Self {
+        self.traced.push(target.into());
+        self
+    }
+
+    /// The most verbose level `-v` flags can ask for.
+    pub fn level_for_verbosity(verbosity: usize) -> LevelFilter {
+        match verbosity {
+            0 => LevelFilter::Warn,
+            1 => LevelFilter::Info,
+            2 => LevelFilter::Debug,
+            _ => LevelFilter::Trace,
+        }
+    }
+
+    pub fn init(self) -> Result<(), SetLoggerError> {
+        let max_level = if self.traced.is_empty() {
+            self.level
+        } else {
+            LevelFilter::Trace
+        };
+        log::set_boxed_logger(Box::new(self))?;
+        log::set_max_level(max_level);
+        Ok(())
+    }
+
+    fn level_for(&self, target: &str) -> LevelFilter {
+        let target = short_target(target);
+        let is_traced = self.traced.iter().any(|traced| {
+            target == traced
+                || target
+                    .strip_prefix(traced.as_str())
+                    .is_some_and(|rest| rest.starts_with("::"))
+        });
+        if is_traced {
+            LevelFilter::Trace
+        } else {
+            self.level
+        }
+    }
+}
+
+/// Drops the crate name from module path targets.
+fn short_target(target: &str) -> &str {
+    target
+        .strip_prefix(concat!(env!("CARGO_CRATE_NAME"), "::"))
+        .unwrap_or(target)
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level_for(metadata.target())
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            eprintln!(
+                "[{} {}] {}",
+                record.level(),
+                short_target(record.target()),
+                record.args()
+            );
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn traced_targets() {
+        let logger = Logger::new(LevelFilter::Warn).trace("day11");
+        assert_eq!(
+            logger.level_for("advent_of_code_2021_rust::day11"),
+            LevelFilter::Trace
+        );
+        assert_eq!(
+            logger.level_for("advent_of_code_2021_rust::day11::part2"),
+            LevelFilter::Trace
+        );
+        assert_eq!(
+            logger.level_for("advent_of_code_2021_rust::day1"),
+            LevelFilter::Warn
+        );
+    }
+}
@@ -1,14 +1,38 @@
 use anyhow::anyhow;
-use std::{
-    env::args,
-    io::{stdin, StdinLock},
-};
+use std::{env::args, io::stdout, path::Path};
 
-use advent_of_code_2021_rust::*;
+use advent_of_code_2021_rust::{
+    bench, input::InputSource, logging::Logger, parse_error::ParseError, runner, scaffold, solution,
+};
 
 fn main() -> anyhow::Result<()> {
-    let mut args = args();
-    args.next();
+    let result = run();
+    if let Err(err) = &result {
+        if let Some(parse_error) = err.chain().find_map(|e| e.downcast_ref::<ParseError>()) {
+            eprintln!("{}\n", parse_error.snippet());
+        }
+    }
+    result
+}
+
+fn run() -> anyhow::Result<()> {
+    let mut args: Vec<String> = args().skip(1).collect();
+    init_logging(&mut args)?;
+    match args.first().map(String::as_str) {
+        Some("run-all") => run_all(&args[1..]),
+        Some("bench") => run_bench(args[1..].to_vec()),
+        Some("new-day") => new_day(&args[1..]),
+        _ => run_one(args),
+    }
+}
+
+/// `<day> <part> [--input path]`
+///
+/// Reads `inputs/dayNN.txt` unless `--input` is given; `--input -` reads stdin.
+fn run_one(mut args: Vec<String>) -> anyhow::Result<()> {
+    let input_path = take_option(&mut args, "--input")?;
+
+    let mut args = args.iter();
     let day = args
         .next()
         .ok_or(anyhow!("Must provide day number argument"))?;
@@ -19,36 +43,131 @@ fn main() -> anyhow::Result<()> {
         .ok_or(anyhow!("Must provide part number argument"))?;
     let part = part.parse::<u8>()?;
 
-    type PartFn = Box<dyn Fn(StdinLock<'static>) -> anyhow::Result<String>>;
-    let part_fn: PartFn = Box::new(match (day, part) {
-        (1, 1) => day01::part1,
-        (1, 2) => day01::part2,
-        (2, 1) => day02::part1,
-        (2, 2) => day02::part2,
-        (3, 1) => day03::part1,
-        (3, 2) => day03::part2,
-        (4, 1) => day04::part1,
-        (4, 2) => day04::part2,
-        (5, 1) => day05::part1,
-        (5, 2) => day05::part2,
-        (6, 1) => day06::part1,
-        (6, 2) => day06::part2,
-        (7, 1) => day07::part1,
-        (7, 2) => day07::part2,
-        (8, 1) => day08::part1,
-        (8, 2) => day08::part2,
-        (9, 1) => day09::part1,
-        (9, 2) => day09::part2,
-        (10, 1) => day10::part1,
-        (10, 2) => day10::part2,
-        (11, 1) => day11::part1,
-        (11, 2) => day11::part2,
-        _ => return Err(anyhow!("Invalid day/part")),
-    });
+    let solution = solution::find(day)?;
 
-    let stdin = Box::leak(Box::new(stdin()));
-    let handle = stdin.lock();
-    let output = part_fn(handle)?;
+    let mut input = InputSource::from_arg(input_path.as_deref(), day).open()?;
+    let output = solution.run(part, &mut input)?;
     println!("{}", output);
     Ok(())
 }
+
+/// `run-all [fixtures-dir]`
+///
+/// Checks every day against the fixtures in `tests/fixtures/day-N/` unless another directory is
+/// given. These hold expected answers as well as inputs, so this doesn't read `inputs/` as a
+/// single run does.
+fn run_all(args: &[String]) -> anyhow::Result<()> {
+    let fixtures_dir = args
+        .first()
+        .map(String::as_str)
+        .unwrap_or(runner::DEFAULT_FIXTURES_DIR);
+    let results = runner::run_all(Path::new(fixtures_dir))?;
+    runner::write_table(&results, &mut stdout())?;
+
+    let failures = results
+        .iter()
+        .filter(|result| {
+            matches!(
+                result.status(),
+                runner::Status::Fail | runner::Status::Error
+            )
+        })
+        .count();
+    if failures > 0 {
+        return Err(anyhow!("{} of {} runs failed", failures, results.len()));
+    }
+    Ok(())
+}
+
+/// `bench <day> <part> [--iterations N] [--input path] [--baseline file] [--save file]`
+///
+/// The input is found as for a single run. `--baseline` compares against previously saved
+/// results; `--save` records these results into the given file, replacing any earlier entry for
+/// the same day and part.
+fn run_bench(mut args: Vec<String>) -> anyhow::Result<()> {
+    let iterations = match take_option(&mut args, "--iterations")? {
+        Some(iterations) => iterations.parse()?,
+        None => bench::DEFAULT_ITERATIONS,
+    };
+    let input_path = take_option(&mut args, "--input")?;
+    let baseline_path = take_option(&mut args, "--baseline")?;
+    let save_path = take_option(&mut args, "--save")?;
+
+    let mut args = args.iter();
+    let day = args
+        .next()
+        .ok_or(anyhow!("Must provide day number argument"))?
+        .parse::<u8>()?;
+    let part = args
+        .next()
+        .ok_or(anyhow!("Must provide part number argument"))?
+        .parse::<u8>()?;
+    let solution = solution::find(day)?;
+
+    let input = InputSource::from_arg(input_path.as_deref(), day).read_to_string()?;
+
+    let result = bench::bench(solution, part, &input, iterations)?;
+    let baseline = baseline_path
+        .map(|path| bench::Baseline::load(Path::new(&path)))
+        .transpose()?;
+    bench::write_report(&result, baseline.as_ref(), &mut stdout())?;
+
+    if let Some(path) = save_path {
+        let path = Path::new(&path);
+        let mut saved = bench::Baseline::load_or_default(path)?;
+        saved.record(&result);
+        saved.save(path)?;
+    }
+    Ok(())
+}
+
+/// `new-day <day> <title>`
+///
+/// Must be run from the repository root.
+fn new_day(args: &[String]) -> anyhow::Result<()> {
+    let day = args
+        .first()
+        .ok_or(anyhow!("Must provide day number argument"))?
+        .parse::<u8>()?;
+    let title = args[1..].join(" ");
+    if title.is_empty() {
+        return Err(anyhow!("Must provide the puzzle title"));
+    }
+    for path in scaffold::new_day(Path::new("."), day, &title)? {
+        println!("wrote {}", path.display());
+    }
+    Ok(())
+}
+
+/// Global options: `-v` (repeatable, or `-vv`/`-vvv`) raises the log level for everything, and
+/// `--trace <target>` (repeatable) logs everything under a target such as `day11`.
+fn init_logging(args: &mut Vec<String>) -> anyhow::Result<()> {
+    let mut verbosity = 0;
+    args.retain(|arg| {
+        let is_verbosity =
+            arg.len() > 1 && arg.starts_with('-') && arg[1..].chars().all(|c| c == 'v');
+        if is_verbosity {
+            verbosity += arg.len() - 1;
+        }
+        !is_verbosity
+    });
+    let mut logger = Logger::new(Logger::level_for_verbosity(verbosity));
+    while let Some(target) = take_option(args, "--trace")? {
+        logger = logger.trace(target);
+    }
+    logger.init()?;
+    Ok(())
+}
+
+/// Removes `name` and the value following it from `args`, returning the value.
+fn take_option(args: &mut Vec<String>, name: &str) -> anyhow::Result<Option<String>> {
+    match args.iter().position(|arg| arg == name) {
+        Some(idx) if idx + 1 < args.len() => {
+            let value = args.remove(idx + 1);
+            args.remove(idx);
+            Ok(Some(value))
+        }
+        Some(_) => Err(anyhow!("{} needs a value", name)),
+        None => Ok(None),
+    }
+}
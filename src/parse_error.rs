@@ -0,0 +1,118 @@
+use std::fmt::Display;
+
+/// A problem with the puzzle input, pointing at where in the input it is.
+///
+/// Built from the offending line, or a slice of it, then placed with [`on_line`](Self::on_line):
+///
+/// ```
+/// # use advent_of_code_2021_rust::parse_error::ParseError;
+/// let line = "forward x";
+/// let err = ParseError::at(line, &line[8..], "a number").on_line(2);
+/// assert_eq!(err.to_string(), "line 3, column 9: expected a number, found \"x\"");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based
+    pub line: usize,
+    /// 1-based, counted in characters
+    pub column: usize,
+    /// The whole offending line
+    pub text: String,
+    /// Length in characters of the offending part of `text`
+    pub len: usize,
+    pub expected: String,
+}
+
+impl ParseError {
+    /// An error covering all of `text`, which is taken to be the first line of the input.
+    pub fn new(text: &str, expected: impl Into<String>) -> Self {
+        ParseError {
+            line: 1,
+            column: 1,
+            text: text.to_owned(),
+            len: text.chars().count(),
+            expected: expected.into(),
+        }
+    }
+
+    /// An error covering `fragment`, which should be a slice of `text`. Anything else is looked
+    /// for in `text`, falling back to covering all of it.
+    pub fn at(text: &str, fragment: &str, expected: impl Into<String>) -> Self {
+        let start = text.as_ptr() as usize;
+        let offset = (fragment.as_ptr() as usize)
+            .checked_sub(start)
+            .filter(|offset| {
+                offset + fragment.len() <= text.len() && text.is_char_boundary(*offset)
+            })
+            .or_else(|| text.find(fragment));
+        let mut err = Self::new(text, expected);
+        if let Some(offset) = offset {
+            err.column = text[..offset].chars().count() + 1;
+            err.len = fragment.chars().count();
+        }
+        err
+    }
+
+    /// Sets the 0-based index of the line the error is on.
+    pub fn on_line(mut self, line_idx: usize) -> Self {
+        self.line = line_idx + 1;
+        self
+    }
+
+    /// Moves the error down by `lines`, for errors from parsing part of a larger input.
+    pub fn shift_lines(mut self, lines: usize) -> Self {
+        self.line += lines;
+        self
+    }
+
+    /// Narrows the error down to the character at the 0-based `column_idx`.
+    pub fn at_column(mut self, column_idx: usize) -> Self {
+        self.column = column_idx + 1;
+        self.len = 1;
+        self
+    }
+
+    /// The offending part of the line.
+    pub fn found(&self) -> String {
+        self.text
+            .chars()
+            .skip(self.column - 1)
+            .take(self.len)
+            .collect()
+    }
+
+    /// The offending line, with the offending part underlined by carets:
+    ///
+    /// ```text
+    ///   3 | forward x
+    ///     |         ^ expected a number
+    /// ```
+    pub fn snippet(&self) -> String {
+        let gutter = self.line.to_string().len();
+        format!(
+            "{:>gw$} | {}\n{:>gw$} | {}{} expected {}",
+            self.line,
+            self.text,
+            "",
+            " ".repeat(self.column - 1),
+            "^".repeat(self.len.max(1)),
+            self.expected,
+            gw = gutter + 2,
+        )
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, found {:?}",
+            self.line,
+            self.column,
+            self.expected,
+            self.found()
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
@@ -0,0 +1,186 @@
+use anyhow::anyhow;
+use std::{
+    fmt::Display,
+    fs::read_to_string,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    answer::Answer,
+    solution::{self, Solution},
+};
+
+/// Where `run-all` looks for each day's inputs and expected answers when no directory is given.
+/// Unlike [`crate::input::DEFAULT_INPUTS_DIR`], which holds one bare input per day, every day
+/// here has its own `day-N` directory of fixtures.
+pub const DEFAULT_FIXTURES_DIR: &str = "tests/fixtures";
+
+/// The outcome of running one part of one day against its stored input.
+pub struct RunResult {
+    pub day: u8,
+    pub part: u8,
+    /// `None` if the day has no input file
+    pub answer: Option<anyhow::Result<Answer>>,
+    pub elapsed: Duration,
+    pub expected: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail,
+    /// There is no expected answer on disk to compare against
+    Unchecked,
+    NoInput,
+    Error,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = match self {
+            Status::Pass => "pass",
+            Status::Fail => "FAIL",
+            Status::Unchecked => "-",
+            Status::NoInput => "no input",
+            Status::Error => "ERROR",
+        };
+        f.pad(text)
+    }
+}
+
+impl RunResult {
+    pub fn status(&self) -> Status {
+        match (&self.answer, &self.expected) {
+            (None, _) => Status::NoInput,
+            (Some(Err(_)), _) => Status::Error,
+            (Some(Ok(_)), None) => Status::Unchecked,
+            (Some(Ok(answer)), Some(expected)) if answer.to_string() == *expected => Status::Pass,
+            (Some(Ok(_)), Some(_)) => Status::Fail,
+        }
+    }
+}
+
+/// What a scaffolded expected-answer file holds until the real answer is known. Parts with it
+/// are left unchecked rather than failing.
+pub const PLACEHOLDER_ANSWER: &str = "unsolved";
+
+/// The input run by default, and the only one `run-all` uses.
+pub const DEFAULT_INPUT_NAME: &str = "input";
+
+/// Directory holding a day's inputs and their expected answers.
+pub fn day_dir(inputs_dir: &Path, day: u8) -> PathBuf {
+    inputs_dir.join(format!("day-{}", day))
+}
+
+/// `input.txt` for the default input, otherwise `<name>-input.txt`.
+pub fn input_path(day_dir: &Path, name: &str) -> PathBuf {
+    if name == DEFAULT_INPUT_NAME {
+        day_dir.join("input.txt")
+    } else {
+        day_dir.join(format!("{}-input.txt", name))
+    }
+}
+
+/// `expected-part-N.txt` for the default input, otherwise `<name>-expected-part-N.txt`.
+pub fn expected_path(day_dir: &Path, name: &str, part: u8) -> PathBuf {
+    if name == DEFAULT_INPUT_NAME {
+        day_dir.join(format!("expected-part-{}.txt", part))
+    } else {
+        day_dir.join(format!("{}-expected-part-{}.txt", name, part))
+    }
+}
+
+/// Runs both parts of every registered day against the inputs in `inputs_dir`. Fails if there's
+/// no such directory or none of the days have an input in it, as nothing would be checked.
+pub fn run_all(inputs_dir: &Path) -> anyhow::Result<Vec<RunResult>> {
+    if !inputs_dir.is_dir() {
+        return Err(anyhow!("{} is not a directory", inputs_dir.display()));
+    }
+    let results: Vec<RunResult> = solution::all()
+        .iter()
+        .flat_map(|solution| run_day(*solution, inputs_dir))
+        .collect();
+    if results.iter().all(|result| result.answer.is_none()) {
+        return Err(anyhow!(
+            "None of the days have an input in {}",
+            inputs_dir.display()
+        ));
+    }
+    Ok(results)
+}
+
+fn run_day(solution: &dyn Solution, inputs_dir: &Path) -> Vec<RunResult> {
+    let dir = day_dir(inputs_dir, solution.day());
+    let input = read_to_string(input_path(&dir, DEFAULT_INPUT_NAME)).ok();
+    [1, 2]
+        .into_iter()
+        .map(|part| {
+            let expected = read_to_string(expected_path(&dir, DEFAULT_INPUT_NAME, part))
+                .ok()
+                .map(|expected| expected.trim_end().to_owned())
+                .filter(|expected| expected != PLACEHOLDER_ANSWER);
+            let start = Instant::now();
+            let answer = input
+                .as_ref()
+                .map(|input| solution.run(part, &mut input.as_bytes()));
+            RunResult {
+                day: solution.day(),
+                part,
+                answer,
+                elapsed: start.elapsed(),
+                expected,
+            }
+        })
+        .collect()
+}
+
+pub fn write_table(results: &[RunResult], out: &mut impl Write) -> io::Result<()> {
+    let answers: Vec<String> = results
+        .iter()
+        .map(|result| match &result.answer {
+            Some(Ok(answer)) => answer.to_string(),
+            Some(Err(err)) => err.to_string(),
+            None => String::new(),
+        })
+        .collect();
+    // Answers drawn over several lines, like letters in dots, continue under their first line
+    let answer_width = answers
+        .iter()
+        .flat_map(|answer| answer.lines())
+        .map(|line| line.len())
+        .chain(Some("Answer".len()))
+        .max()
+        .unwrap_or_default();
+
+    writeln!(
+        out,
+        "{:>3}  {:>4}  {:<aw$}  {:>12}  Status",
+        "Day",
+        "Part",
+        "Answer",
+        "Time",
+        aw = answer_width
+    )?;
+    for (result, answer) in results.iter().zip(answers.iter()) {
+        let mut lines = answer.lines();
+        writeln!(
+            out,
+            "{:>3}  {:>4}  {:<aw$}  {:>12}  {}",
+            result.day,
+            result.part,
+            lines.next().unwrap_or_default(),
+            match result.answer {
+                Some(_) => format!("{:.2?}", result.elapsed),
+                None => String::new(),
+            },
+            result.status(),
+            aw = answer_width
+        )?;
+        for line in lines {
+            writeln!(out, "{:>3}  {:>4}  {}", "", "", line)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,115 @@
+use anyhow::anyhow;
+use std::{
+    fs::{create_dir_all, read_to_string, write},
+    path::{Path, PathBuf},
+};
+
+use crate::runner;
+
+const MODULE_TEMPLATE: &str = r#"use std::io::BufRead;
+
+use crate::answer::Answer;
+
+crate::solution!(__DAY__, __TITLE__);
+
+pub fn part1(_input: &Input) -> anyhow::Result<Answer> {
+    Err(anyhow::anyhow!("Part 1 is not implemented yet"))
+}
+
+pub fn part2(_input: &Input) -> anyhow::Result<Answer> {
+    Err(anyhow::anyhow!("Part 2 is not implemented yet"))
+}
+
+pub type Input = Vec<String>;
+
+pub fn parse_input(input: impl BufRead) -> anyhow::Result<Input> {
+    Ok(input.lines().collect::<Result<_, _>>()?)
+}
+"#;
+
+/// Creates the module and fixture files for a new day under the repository at `root`, and adds
+/// the module to the `days!` list in `src/lib.rs`. Refuses to touch anything if any of the files
+/// already exist or the day is already registered.
+///
+/// The input starts empty and the expected answers hold [`runner::PLACEHOLDER_ANSWER`], so the
+/// day's acceptance tests are ignored until the answers are filled in.
+///
+/// Returns the paths created or changed.
+pub fn new_day(root: &Path, day: u8, title: &str) -> anyhow::Result<Vec<PathBuf>> {
+    let module = format!("day{:02}", day);
+    let module_path = root.join("src").join(format!("{}.rs", module));
+    let lib_path = root.join("src").join("lib.rs");
+    let fixture_dir = runner::day_dir(&root.join(runner::DEFAULT_FIXTURES_DIR), day);
+    let input_path = runner::input_path(&fixture_dir, runner::DEFAULT_INPUT_NAME);
+    let expected_paths =
+        [1, 2].map(|part| runner::expected_path(&fixture_dir, runner::DEFAULT_INPUT_NAME, part));
+
+    let lib = read_to_string(&lib_path)
+        .map_err(|err| anyhow!("Could not read {}: {}", lib_path.display(), err))?;
+    let lib = register(&lib, &module)?;
+    for path in expected_paths.iter().chain([&input_path, &module_path]) {
+        if path.exists() {
+            return Err(anyhow!("{} already exists", path.display()));
+        }
+    }
+
+    let source = MODULE_TEMPLATE
+        .replace("__DAY__", &day.to_string())
+        .replace("__TITLE__", &format!("{:?}", title));
+    write(&module_path, source)?;
+    create_dir_all(&fixture_dir)?;
+    write(&input_path, "")?;
+    for path in expected_paths.iter() {
+        write(path, format!("{}\n", runner::PLACEHOLDER_ANSWER))?;
+    }
+    write(&lib_path, lib)?;
+
+    let mut changed = vec![module_path, lib_path, input_path];
+    changed.extend(expected_paths);
+    Ok(changed)
+}
+
+/// Adds `module` to the `days!` invocation in the source of `lib.rs`, keeping the list sorted.
+fn register(lib: &str, module: &str) -> anyhow::Result<String> {
+    let start = lib
+        .find("days! {")
+        .ok_or_else(|| anyhow!("Could not find the days! list in lib.rs"))?
+        + "days! {".len();
+    let end = start
+        + lib[start..]
+            .find('}')
+            .ok_or_else(|| anyhow!("The days! list in lib.rs is not closed"))?;
+    let mut modules: Vec<&str> = lib[start..end]
+        .split(',')
+        .map(str::trim)
+        .filter(|module| !module.is_empty())
+        .collect();
+    if modules.contains(&module) {
+        return Err(anyhow!("{} is already registered in lib.rs", module));
+    }
+    modules.push(module);
+    modules.sort_unstable();
+
+    let mut out = lib[..start].to_owned();
+    out.push('\n');
+    for module in modules {
+        out.push_str(&format!("    {},\n", module));
+    }
+    out.push_str(&lib[end..]);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn register_keeps_days_sorted() {
+        let lib = "pub mod helpers;\n\ndays! {\n    day01,\n    day03,\n}\n";
+        assert_eq!(
+            register(lib, "day02").unwrap(),
+            "pub mod helpers;\n\ndays! {\n    day01,\n    day02,\n    day03,\n}\n"
+        );
+        assert!(register(lib, "day03").is_err());
+    }
+}
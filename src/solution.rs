@@ -0,0 +1,116 @@
+use anyhow::anyhow;
+use std::{any::Any, io::BufRead};
+
+use crate::answer::Answer;
+
+/// A day's parsed input, type-erased so every day fits behind the same trait.
+pub type Parsed = Box<dyn Any>;
+
+/// A single day's puzzle, as seen by the binary, the tests and any other tooling.
+///
+/// Day modules don't implement this by hand; they call [`solution!`](crate::solution!) with their
+/// day number and title, which forwards to the module's `parse_input`, `part1` and `part2`.
+/// Parsing is kept apart from solving so the two can be timed separately.
+pub trait Solution: Sync {
+    fn day(&self) -> u8;
+
+    fn title(&self) -> &'static str;
+
+    fn parse(&self, input: &mut dyn BufRead) -> anyhow::Result<Parsed>;
+
+    fn part1(&self, input: &Parsed) -> anyhow::Result<Answer>;
+
+    fn part2(&self, input: &Parsed) -> anyhow::Result<Answer>;
+
+    fn solve(&self, part: u8, input: &Parsed) -> anyhow::Result<Answer> {
+        match part {
+            1 => self.part1(input),
+            2 => self.part2(input),
+            _ => Err(anyhow!(
+                "Invalid part {} for day {} (available parts: 1, 2)",
+                part,
+                self.day()
+            )),
+        }
+    }
+
+    /// Parses `input` and solves `part` of the puzzle with it.
+    fn run(&self, part: u8, input: &mut dyn BufRead) -> anyhow::Result<Answer> {
+        let parsed = self.parse(input)?;
+        self.solve(part, &parsed)
+    }
+}
+
+/// Declares the `Day` type for the calling module and implements [`Solution`] for it.
+///
+/// The module must define a type called `Input`, `parse_input` taking `impl BufRead` and
+/// returning it, and `part1` and `part2` taking `&Input`.
+#[macro_export]
+macro_rules! solution {
+    ($day:expr, $title:expr) => {
+        pub struct Day;
+
+        impl Day {
+            fn downcast(input: &$crate::solution::Parsed) -> anyhow::Result<&Input> {
+                input.downcast_ref::<Input>().ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Parsed input for day {} is not a {}",
+                        $day,
+                        std::any::type_name::<Input>()
+                    )
+                })
+            }
+        }
+
+        impl $crate::solution::Solution for Day {
+            fn day(&self) -> u8 {
+                $day
+            }
+
+            fn title(&self) -> &'static str {
+                $title
+            }
+
+            fn parse(
+                &self,
+                input: &mut dyn std::io::BufRead,
+            ) -> anyhow::Result<$crate::solution::Parsed> {
+                Ok(Box::new(parse_input(input)?))
+            }
+
+            fn part1(
+                &self,
+                input: &$crate::solution::Parsed,
+            ) -> anyhow::Result<$crate::answer::Answer> {
+                part1(Self::downcast(input)?)
+            }
+
+            fn part2(
+                &self,
+                input: &$crate::solution::Parsed,
+            ) -> anyhow::Result<$crate::answer::Answer> {
+                part2(Self::downcast(input)?)
+            }
+        }
+    };
+}
+
+/// Every registered solution, ordered by day.
+pub fn all() -> &'static [&'static dyn Solution] {
+    crate::SOLUTIONS
+}
+
+pub fn find(day: u8) -> anyhow::Result<&'static dyn Solution> {
+    all()
+        .iter()
+        .find(|solution| solution.day() == day)
+        .copied()
+        .ok_or_else(|| {
+            let available: Vec<String> = all().iter().map(|s| s.day().to_string()).collect();
+            anyhow!(
+                "No solution for day {} (available days: {})",
+                day,
+                available.join(", ")
+            )
+        })
+}
@@ -1,33 +1,51 @@
 use advent_of_code_2021_rust::*;
-use std::fs::{read_to_string, File};
-use std::io::BufReader;
+use std::path::Path;
 
-macro_rules! test_day {
-    ($name:ident, $day:expr, $part:expr, $function:expr) => {
+/// Runs one part of a day against a named input in its fixture directory. Any attributes, such
+/// as `#[ignore]`, are passed on to the test.
+macro_rules! fixture_test {
+    ($(#[$attr:meta])* $name:ident, $day:expr, $part:expr, $input_name:expr) => {
         #[test]
+        $(#[$attr])*
         fn $name() {
-            let input = File::open(format!("tests/fixtures/day-{}/input.txt", $day)).unwrap();
-            let expected_output = read_to_string(format!(
-                "tests/fixtures/day-{}/expected-part-{}.txt",
-                $day, $part
-            ))
-            .unwrap();
-            let output = $function(BufReader::new(input)).unwrap();
-            assert_eq!(output, expected_output.trim_end());
+            let dir = runner::day_dir(Path::new(runner::DEFAULT_FIXTURES_DIR), $day);
+            let expected_path = runner::expected_path(&dir, $input_name, $part);
+            let expected_output = std::fs::read_to_string(&expected_path).unwrap_or_else(|err| {
+                panic!(
+                    "Missing expected answer {} ({})",
+                    expected_path.display(),
+                    err
+                )
+            });
+            let input = std::fs::File::open(runner::input_path(&dir, $input_name)).unwrap();
+            let solution = solution::find($day).unwrap();
+            let output = solution.run($part, &mut std::io::BufReader::new(input)).unwrap();
+            assert_eq!(output.to_string(), expected_output.trim_end());
         }
     };
 }
 
-test_day!(day04_1, 4, 1, day04::part1);
-test_day!(day06_1, 6, 1, day06::part1);
-test_day!(day06_2, 6, 2, day06::part2);
-test_day!(day07_1, 7, 1, day07::part1);
-test_day!(day07_2, 7, 2, day07::part2);
-test_day!(day08_1, 8, 1, day08::part1);
-test_day!(day08_2, 8, 2, day08::part2);
-test_day!(day09_1, 9, 1, day09::part1);
-test_day!(day09_2, 9, 2, day09::part2);
-test_day!(day10_1, 10, 1, day10::part1);
-test_day!(day10_2, 10, 2, day10::part2);
-test_day!(day11_1, 11, 1, day11::part1);
-test_day!(day11_2, 11, 2, day11::part2);
+// One `fixture_test!` per part of every input found by build.rs
+include!(concat!(env!("OUT_DIR"), "/fixture_tests.rs"));
+
+// An input with no expected answer beside it fails rather than passing unchecked
+fixture_test!(
+    #[should_panic(expected = "Missing expected answer")]
+    missing_expected_answer_fails,
+    1,
+    1,
+    "no-such"
+);
+
+#[test]
+fn every_day_has_fixtures() {
+    for solution in solution::all() {
+        let dir = runner::day_dir(Path::new(runner::DEFAULT_FIXTURES_DIR), solution.day());
+        assert!(
+            dir.is_dir(),
+            "Day {} has no fixture directory at {}",
+            solution.day(),
+            dir.display()
+        );
+    }
+}